@@ -0,0 +1,17 @@
+// Draws a sprite sheet frame as a solid colour, following the frame's alpha.
+// Used to flash sprites white when they're hit.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0) var<uniform> color: vec4<f32>;
+// The frame's UV rectangle in the sheet: min x, min y, max x, max y.
+@group(2) @binding(1) var<uniform> uv_rect: vec4<f32>;
+@group(2) @binding(2) var sprite_texture: texture_2d<f32>;
+@group(2) @binding(3) var sprite_sampler: sampler;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = mix(uv_rect.xy, uv_rect.zw, mesh.uv);
+    let alpha = textureSample(sprite_texture, sprite_sampler, uv).a;
+    return vec4<f32>(color.rgb, color.a * alpha);
+}
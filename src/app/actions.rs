@@ -0,0 +1,179 @@
+//! The players' launchpad input, sampled once per fixed tick.
+//!
+//! Launchpad systems run in `FixedUpdate` and read [`PlayerActions`] instead of the keyboard
+//! and gamepads, so a round can be recorded and replayed tick by tick.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::coop::{CoopMode, PlayerId},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PlayerActions>();
+    app.init_resource::<PlayerActions>();
+    app.init_resource::<PendingPresses>();
+
+    app.add_systems(
+        Update,
+        latch_presses
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
+    );
+    app.add_systems(
+        FixedUpdate,
+        sample_actions
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
+    );
+}
+
+/// What a player does with their launcher during one tick.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LauncherActions {
+    /// Which way to turn: 1 to the left, -1 to the right, or 0 to hold still.
+    pub turn: i8,
+    pub fire: bool,
+    /// Hand control to the next launcher. Recordings from before there were several launchers
+    /// never switch.
+    #[serde(default)]
+    pub switch: bool,
+}
+
+/// What each player does during one tick.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub struct PlayerActions(pub [LauncherActions; 2]);
+
+impl PlayerActions {
+    pub fn of(&self, player: PlayerId) -> LauncherActions {
+        self.0[player.index()]
+    }
+}
+
+/// The keys a player operates their launcher with.
+struct KeyBindings {
+    left: &'static [KeyCode],
+    right: &'static [KeyCode],
+    fire: KeyCode,
+    switch: KeyCode,
+}
+
+/// A player on their own has the whole keyboard.
+const SOLO_KEYS: KeyBindings = KeyBindings {
+    left: &[KeyCode::KeyA, KeyCode::ArrowLeft],
+    right: &[KeyCode::KeyD, KeyCode::ArrowRight],
+    fire: KeyCode::Space,
+    switch: KeyCode::Tab,
+};
+
+/// Two players split the keyboard down the middle.
+const SPLIT_KEYS: [KeyBindings; 2] = [
+    KeyBindings {
+        left: &[KeyCode::KeyA],
+        right: &[KeyCode::KeyD],
+        fire: KeyCode::Space,
+        switch: KeyCode::Tab,
+    },
+    KeyBindings {
+        left: &[KeyCode::ArrowLeft],
+        right: &[KeyCode::ArrowRight],
+        fire: KeyCode::Enter,
+        switch: KeyCode::ShiftRight,
+    },
+];
+
+fn key_bindings(mode: CoopMode, player: PlayerId) -> &'static KeyBindings {
+    match mode {
+        CoopMode::Solo => &SOLO_KEYS,
+        _ => &SPLIT_KEYS[player.index()],
+    }
+}
+
+/// How far a gamepad stick has to be pushed to turn the launcher.
+const STICK_DEADZONE: f32 = 0.3;
+
+/// The gamepad of each player, in the order they were connected.
+fn player_gamepads<'a>(
+    mode: CoopMode,
+    gamepads: &'a Query<(Entity, &Gamepad)>,
+) -> impl Iterator<Item = (PlayerId, &'a Gamepad)> {
+    let mut connected = gamepads.iter().collect::<Vec<_>>();
+    connected.sort_by_key(|(entity, _)| *entity);
+    mode.players()
+        .iter()
+        .copied()
+        .zip(connected.into_iter().map(|(_, gamepad)| gamepad))
+}
+
+/// Whether fire or switch were pressed since the last tick, for each player. Presses are only
+/// seen for a frame, which may not run a tick at all.
+#[derive(Resource, Default)]
+pub(super) struct PendingPresses {
+    fire: [bool; 2],
+    switch: [bool; 2],
+}
+
+fn latch_presses(
+    mode: Res<CoopMode>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut pending: ResMut<PendingPresses>,
+) {
+    for &player in mode.players() {
+        let keys = key_bindings(*mode, player);
+        if keyboard_input.just_pressed(keys.fire) {
+            pending.fire[player.index()] = true;
+        }
+        if keyboard_input.just_pressed(keys.switch) {
+            pending.switch[player.index()] = true;
+        }
+    }
+    for (player, gamepad) in player_gamepads(*mode, &gamepads) {
+        if gamepad.just_pressed(GamepadButton::South) {
+            pending.fire[player.index()] = true;
+        }
+        if gamepad.just_pressed(GamepadButton::North) {
+            pending.switch[player.index()] = true;
+        }
+    }
+}
+
+pub(super) fn sample_actions(
+    mode: Res<CoopMode>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut pending: ResMut<PendingPresses>,
+    mut actions: ResMut<PlayerActions>,
+) {
+    let mut turns = [0.0; 2];
+    for &player in mode.players() {
+        let keys = key_bindings(*mode, player);
+        if keyboard_input.any_pressed(keys.left.iter().copied()) {
+            turns[player.index()] += 1.0;
+        }
+        if keyboard_input.any_pressed(keys.right.iter().copied()) {
+            turns[player.index()] -= 1.0;
+        }
+    }
+    for (player, gamepad) in player_gamepads(*mode, &gamepads) {
+        let stick = gamepad.left_stick().x + gamepad.dpad().x;
+        if stick.abs() > STICK_DEADZONE {
+            turns[player.index()] -= stick.signum();
+        }
+    }
+
+    for player in PlayerId::ALL {
+        let index = player.index();
+        actions.0[index] = LauncherActions {
+            turn: (turns[index] as i8).clamp(-1, 1),
+            fire: std::mem::take(&mut pending.fire[index]),
+            switch: std::mem::take(&mut pending.switch[index]),
+        };
+    }
+}
@@ -1,175 +1,111 @@
-//! Player sprite animation.
-//! This is based on multiple examples and may be very different for your game.
-//! - [Sprite flipping](https://github.com/bevyengine/bevy/blob/latest/examples/2d/sprite_flipping.rs)
+//! Data-driven sprite animation.
+//! A [`SpriteAnimator`] plays named clips from a [`SpriteAtlas`], so timing, loop modes and
+//! per-frame events live in the atlas files rather than in code.
 //! - [Sprite animation](https://github.com/bevyengine/bevy/blob/latest/examples/2d/sprite_animation.rs)
 //! - [Timers](https://github.com/bevyengine/bevy/blob/latest/examples/time/timers.rs)
 
 use bevy::prelude::*;
-use rand::prelude::*;
 use std::time::Duration;
 
-use crate::{
-    AppSystems, PausableSystems,
-    app::{movement::MovementController, player::PlayerAssets},
-    audio::sound_effect,
-};
+use crate::{AppSystems, PausableSystems, sprite_atlas::SpriteAtlas};
 
 pub(super) fn plugin(app: &mut App) {
-    // Animate and play sound effects based on controls.
-    app.register_type::<PlayerAnimation>();
+    app.add_event::<SpriteAnimationEvent>();
+
+    app.register_type::<SpriteAnimator>();
     app.add_systems(
         Update,
-        (
-            update_animation_timer.in_set(AppSystems::TickTimers),
-            (
-                update_animation_movement,
-                update_animation_atlas,
-                trigger_step_sound_effect,
-            )
-                .chain()
-                .run_if(resource_exists::<PlayerAssets>)
-                .in_set(AppSystems::Update),
-        )
+        animate_sprites
+            .in_set(AppSystems::TickTimers)
             .in_set(PausableSystems),
     );
 }
 
-/// Update the sprite direction and animation state (idling/walking).
-fn update_animation_movement(
-    mut player_query: Query<(&MovementController, &mut Sprite, &mut PlayerAnimation)>,
-) {
-    for (controller, mut sprite, mut animation) in &mut player_query {
-        let dx = controller.intent.x;
-        if dx != 0.0 {
-            sprite.flip_x = dx < 0.0;
-        }
-
-        let animation_state = if controller.intent == Vec2::ZERO {
-            PlayerAnimationState::Idling
-        } else {
-            PlayerAnimationState::Walking
-        };
-        animation.update_state(animation_state);
-    }
-}
-
-/// Update the animation timer.
-fn update_animation_timer(time: Res<Time>, mut query: Query<&mut PlayerAnimation>) {
-    for mut animation in &mut query {
-        animation.update_timer(time.delta());
-    }
-}
-
-/// Update the texture atlas to reflect changes in the animation.
-fn update_animation_atlas(mut query: Query<(&PlayerAnimation, &mut Sprite)>) {
-    for (animation, mut sprite) in &mut query {
-        let Some(atlas) = sprite.texture_atlas.as_mut() else {
-            continue;
-        };
-        if animation.changed() {
-            atlas.index = animation.get_atlas_index();
-        }
-    }
-}
-
-/// If the player is moving, play a step sound effect synchronized with the
-/// animation.
-fn trigger_step_sound_effect(
-    mut commands: Commands,
-    player_assets: Res<PlayerAssets>,
-    mut step_query: Query<&PlayerAnimation>,
-) {
-    for animation in &mut step_query {
-        if animation.state == PlayerAnimationState::Walking
-            && animation.changed()
-            && (animation.frame == 2 || animation.frame == 5)
-        {
-            let rng = &mut rand::thread_rng();
-            let random_step = player_assets.steps.choose(rng).unwrap().clone();
-            commands.spawn(sound_effect(random_step));
-        }
-    }
-}
-
-/// Component that tracks player's animation state.
-/// It is tightly bound to the texture atlas we use.
+/// Plays a clip from a [`SpriteAtlas`] on the entity's [`Sprite`].
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-pub struct PlayerAnimation {
+pub struct SpriteAnimator {
+    atlas: Handle<SpriteAtlas>,
+    clip: String,
+    step: usize,
     timer: Timer,
-    frame: usize,
-    state: PlayerAnimationState,
-}
-
-#[derive(Reflect, PartialEq)]
-pub enum PlayerAnimationState {
-    Idling,
-    Walking,
+    /// The playback speed, where 1 plays the clip at the frame rate given in the atlas.
+    pub speed: f32,
 }
 
-impl PlayerAnimation {
-    /// The number of idle frames.
-    const IDLE_FRAMES: usize = 2;
-    /// The duration of each idle frame.
-    const IDLE_INTERVAL: Duration = Duration::from_millis(500);
-    /// The number of walking frames.
-    const WALKING_FRAMES: usize = 6;
-    /// The duration of each walking frame.
-    const WALKING_INTERVAL: Duration = Duration::from_millis(50);
-
-    fn idling() -> Self {
+impl SpriteAnimator {
+    pub fn new(atlas: Handle<SpriteAtlas>, clip: impl Into<String>) -> Self {
         Self {
-            timer: Timer::new(Self::IDLE_INTERVAL, TimerMode::Repeating),
-            frame: 0,
-            state: PlayerAnimationState::Idling,
+            atlas,
+            clip: clip.into(),
+            step: 0,
+            timer: Timer::new(Duration::ZERO, TimerMode::Repeating),
+            speed: 1.0,
         }
     }
 
-    fn walking() -> Self {
-        Self {
-            timer: Timer::new(Self::WALKING_INTERVAL, TimerMode::Repeating),
-            frame: 0,
-            state: PlayerAnimationState::Walking,
+    /// Switch to another clip, starting from its first frame. Does nothing if the clip is
+    /// already playing.
+    pub fn play(&mut self, clip: &str) {
+        if self.clip != clip {
+            self.clip = clip.to_string();
+            self.step = 0;
+            self.timer.reset();
         }
     }
 
-    pub fn new() -> Self {
-        Self::idling()
+    /// The atlas index currently shown.
+    pub fn atlas_index(&self, atlas: &SpriteAtlas) -> usize {
+        atlas
+            .animation(&self.clip)
+            .map_or(0, |animation| animation.atlas_index(self.step))
     }
+}
 
-    /// Update animation timers.
-    pub fn update_timer(&mut self, delta: Duration) {
-        self.timer.tick(delta);
-        if !self.timer.finished() {
-            return;
-        }
-        self.frame = (self.frame + 1)
-            % match self.state {
-                PlayerAnimationState::Idling => Self::IDLE_FRAMES,
-                PlayerAnimationState::Walking => Self::WALKING_FRAMES,
-            };
-    }
+/// Sent when a [`SpriteAnimator`] reaches a frame that has an event in its atlas, e.g.
+/// `"footstep"`.
+#[derive(Event, Debug, Clone)]
+pub struct SpriteAnimationEvent {
+    pub entity: Entity,
+    pub name: String,
+}
+
+/// Advance every animator and update its sprite to the current frame.
+fn animate_sprites(
+    time: Res<Time>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
+    mut animation_events: EventWriter<SpriteAnimationEvent>,
+    mut query: Query<(Entity, &mut SpriteAnimator, &mut Sprite)>,
+) {
+    for (entity, mut animator, mut sprite) in &mut query {
+        let Some(atlas) = sprite_atlases.get(&animator.atlas) else {
+            continue;
+        };
+        let Some(animation) = atlas.animation(&animator.clip) else {
+            continue;
+        };
 
-    /// Update animation state if it changes.
-    pub fn update_state(&mut self, state: PlayerAnimationState) {
-        if self.state != state {
-            match state {
-                PlayerAnimationState::Idling => *self = Self::idling(),
-                PlayerAnimationState::Walking => *self = Self::walking(),
+        animator.timer.set_duration(animation.frame_duration());
+        let delta = time.delta().mul_f32(animator.speed.max(0.0));
+        animator.timer.tick(delta);
+
+        for _ in 0..animator.timer.times_finished_this_tick() {
+            let step = animation.next_step(animator.step);
+            // An animation played once stays on its last frame without reaching it again.
+            if step == animator.step {
+                break;
+            }
+            animator.step = step;
+            if let Some(name) = animation.event_at(animator.step) {
+                animation_events.write(SpriteAnimationEvent {
+                    entity,
+                    name: name.to_string(),
+                });
             }
         }
-    }
-
-    /// Whether animation changed this tick.
-    pub fn changed(&self) -> bool {
-        self.timer.finished()
-    }
 
-    /// Return sprite index in the atlas.
-    pub fn get_atlas_index(&self) -> usize {
-        match self.state {
-            PlayerAnimationState::Idling => self.frame,
-            PlayerAnimationState::Walking => 6 + self.frame,
+        if let Some(texture_atlas) = sprite.texture_atlas.as_mut() {
+            texture_atlas.index = animation.atlas_index(animator.step);
         }
     }
 }
@@ -0,0 +1,42 @@
+//! The size of the playing field. In the game it follows the primary window, while the headless
+//! simulation sets it directly.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Arena>();
+    app.init_resource::<Arena>();
+    app.add_systems(PreUpdate, fit_arena_to_window);
+}
+
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct Arena {
+    /// Width and height in pixels, centred on the origin.
+    pub size: Vec2,
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(1920.0, 1080.0),
+        }
+    }
+}
+
+impl Arena {
+    pub fn half_size(&self) -> Vec2 {
+        self.size / 2.0
+    }
+}
+
+pub(super) fn fit_arena_to_window(
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    mut arena: ResMut<Arena>,
+) {
+    if let Some(window) = window {
+        arena.set_if_neq(Arena {
+            size: window.size(),
+        });
+    }
+}
@@ -0,0 +1,409 @@
+//! Firework bits and the damage they do. Bits are placed on the shell grid in the workshop,
+//! and each one gives the shells it's packed into a flavour that some UAPs resist, some are
+//! weak to, and shielded ones can only be hurt by. The grid's rows are divided into stages: the
+//! shell carries the bits of the first stage, and bursts into shells carrying the next.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ShellDesign>();
+    app.init_resource::<ShellDesign>();
+}
+
+/// The width and height of the shell grid, in cells.
+pub const SHELL_GRID_SIZE: usize = 8;
+
+/// The most stages a shell can have, including the one fired from the launcher.
+pub const MAX_STAGES: usize = 3;
+
+/// The most shells a shell can burst into.
+const MAX_SPLIT_COUNT: u32 = 8;
+
+/// How much the spread of a stage changes with each step, in degrees.
+const SPREAD_STEP_DEGREES: f32 = 15.0;
+
+/// The widest a stage can be fanned out, in degrees.
+const MAX_SPREAD_DEGREES: f32 = 180.0;
+
+/// How much a stage's fuse changes with each step, in seconds.
+const FUSE_STEP_SECS: f32 = 0.25;
+
+/// The longest fuse a stage can have, in seconds.
+const MAX_FUSE_SECS: f32 = 2.0;
+
+/// The flavour of a bit, which decides what it's good against.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageTag {
+    Citrus,
+    Spicy,
+    Sweet,
+}
+
+impl DamageTag {
+    pub const ALL: [Self; 3] = [Self::Citrus, Self::Spicy, Self::Sweet];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Citrus => "Citrus",
+            Self::Spicy => "Spicy",
+            Self::Sweet => "Sweet",
+        }
+    }
+
+    /// The colour of bursts, shields and labels of this flavour.
+    pub fn color(self) -> Color {
+        match self {
+            Self::Citrus => Color::srgb(1.0, 0.9, 0.2),
+            Self::Spicy => Color::srgb(1.0, 0.3, 0.2),
+            Self::Sweet => Color::srgb(1.0, 0.5, 0.9),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A bit that can be packed into a shell.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bit {
+    Lemon,
+    Pineapple,
+    PepperRed,
+    PepperGreen,
+    Honey,
+    Cookie,
+}
+
+impl Bit {
+    pub const ALL: [Self; 6] = [
+        Self::Lemon,
+        Self::Pineapple,
+        Self::PepperRed,
+        Self::PepperGreen,
+        Self::Honey,
+        Self::Cookie,
+    ];
+
+    pub fn tag(self) -> DamageTag {
+        match self {
+            Self::Lemon | Self::Pineapple => DamageTag::Citrus,
+            Self::PepperRed | Self::PepperGreen => DamageTag::Spicy,
+            Self::Honey | Self::Cookie => DamageTag::Sweet,
+        }
+    }
+
+    /// The path of the bit's sprite.
+    pub fn image_path(self) -> &'static str {
+        match self {
+            Self::Lemon => "images/FreePixelFood/Sprite/Food/Lemon.png",
+            Self::Pineapple => "images/FreePixelFood/Sprite/Food/Pineapple.png",
+            Self::PepperRed => "images/FreePixelFood/Sprite/Food/PepperRed.png",
+            Self::PepperGreen => "images/FreePixelFood/Sprite/Food/PepperGreen.png",
+            Self::Honey => "images/FreePixelFood/Sprite/Food/Honey.png",
+            Self::Cookie => "images/FreePixelFood/Sprite/Food/Cookie.png",
+        }
+    }
+}
+
+/// How a shell's damage is split between flavours: every bit in it adds an equal share of its
+/// flavour. A shell without bits does plain damage.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DamageMix {
+    counts: [u32; 3],
+}
+
+impl DamageMix {
+    pub fn from_bits(bits: impl IntoIterator<Item = Bit>) -> Self {
+        let mut mix = Self::default();
+        for bit in bits {
+            mix.counts[bit.tag().index()] += 1;
+        }
+        mix
+    }
+
+    /// The number of bits in the shell.
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// The colours of the flavours blended by their shares, if the shell has any bits.
+    pub fn color(&self) -> Option<Color> {
+        if self.total() == 0 {
+            return None;
+        }
+        let color = DamageTag::ALL
+            .into_iter()
+            .map(|tag| tag.color().to_linear() * self.share(tag))
+            .fold(LinearRgba::NONE, |blend, color| blend + color);
+        Some(color.into())
+    }
+
+    /// The share of the damage that's of the given flavour, between 0 and 1.
+    pub fn share(&self, tag: DamageTag) -> f32 {
+        match self.total() {
+            0 => 0.0,
+            total => self.counts[tag.index()] as f32 / total as f32,
+        }
+    }
+
+    /// The flavour with the largest share, if the shell has any bits.
+    pub fn dominant(&self) -> Option<DamageTag> {
+        DamageTag::ALL
+            .into_iter()
+            .filter(|&tag| self.counts[tag.index()] > 0)
+            .max_by_key(|&tag| self.counts[tag.index()])
+    }
+}
+
+/// What a UAP is shielded by, resists or is weak to.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Defences {
+    /// Only damage of this flavour gets through.
+    pub shield: Option<DamageTag>,
+    /// Damage of this flavour is halved.
+    pub resistance: Option<DamageTag>,
+    /// Damage of this flavour is doubled.
+    pub weakness: Option<DamageTag>,
+}
+
+impl Defences {
+    /// How much of a shell's damage these defences let through, where 1 is all of it.
+    pub fn multiplier(&self, mix: &DamageMix) -> f32 {
+        if mix.total() == 0 {
+            return if self.shield.is_some() { 0.0 } else { 1.0 };
+        }
+        DamageTag::ALL
+            .into_iter()
+            .map(|tag| mix.share(tag) * self.tag_multiplier(tag))
+            .sum()
+    }
+
+    fn tag_multiplier(&self, tag: DamageTag) -> f32 {
+        if self.shield.is_some_and(|shield| shield != tag) {
+            0.0
+        } else if self.weakness == Some(tag) {
+            2.0
+        } else if self.resistance == Some(tag) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+}
+
+/// A band of rows of the shell grid, and how the shells carrying its bits fly.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Stage {
+    /// How many rows of the grid the stage's bits are packed in.
+    pub rows: usize,
+    /// How many shells each shell of the stage before bursts into. Always 1 for the first stage.
+    pub count: u32,
+    /// The angle the stage's shells are fanned out over, in degrees.
+    pub spread_degrees: f32,
+    /// How long the stage's shells fly before they burst on their own, in seconds, or 0 for
+    /// them to only burst on contact.
+    pub fuse_secs: f32,
+}
+
+impl Stage {
+    /// The stage fired from the launcher, bursting only on contact.
+    fn primary(rows: usize) -> Self {
+        Self {
+            rows,
+            count: 1,
+            spread_degrees: 0.0,
+            fuse_secs: 0.0,
+        }
+    }
+
+    /// A stage splitting into three shells, which burst shortly after.
+    fn split(rows: usize) -> Self {
+        Self {
+            rows,
+            count: 3,
+            spread_degrees: 60.0,
+            fuse_secs: 0.5,
+        }
+    }
+
+    pub fn step_count(&mut self, steps: i32) {
+        self.count = self
+            .count
+            .saturating_add_signed(steps)
+            .clamp(1, MAX_SPLIT_COUNT);
+    }
+
+    pub fn step_spread(&mut self, steps: i32) {
+        self.spread_degrees = (self.spread_degrees + steps as f32 * SPREAD_STEP_DEGREES)
+            .clamp(0.0, MAX_SPREAD_DEGREES);
+    }
+
+    pub fn step_fuse(&mut self, steps: i32) {
+        self.fuse_secs = (self.fuse_secs + steps as f32 * FUSE_STEP_SECS).clamp(0.0, MAX_FUSE_SECS);
+    }
+}
+
+/// The bits packed into the player's shells, laid out on the workshop's shell grid row by row,
+/// and the stages the rows are divided into from the top down.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+#[serde(try_from = "SavedShellDesign")]
+pub struct ShellDesign {
+    cells: Vec<Option<Bit>>,
+    /// Between one and [`MAX_STAGES`], the first being the one fired from the launcher, dividing
+    /// the grid's rows between them.
+    stages: Vec<Stage>,
+}
+
+/// A [`ShellDesign`] as saved, before its stages are checked.
+#[derive(Deserialize)]
+struct SavedShellDesign {
+    cells: Vec<Option<Bit>>,
+    /// Designs from before shells had stages have a single one.
+    #[serde(default = "ShellDesign::single_stage")]
+    stages: Vec<Stage>,
+}
+
+impl TryFrom<SavedShellDesign> for ShellDesign {
+    type Error = String;
+
+    fn try_from(saved: SavedShellDesign) -> Result<Self, Self::Error> {
+        if saved.stages.is_empty() || saved.stages.len() > MAX_STAGES {
+            return Err(format!(
+                "a shell design needs between one and {MAX_STAGES} stages"
+            ));
+        }
+        let rows = saved.stages.iter().map(|stage| stage.rows);
+        if rows.clone().any(|rows| rows == 0) || rows.sum::<usize>() != SHELL_GRID_SIZE {
+            return Err("a shell design's stages need to divide up the grid's rows".into());
+        }
+        Ok(Self {
+            cells: saved.cells,
+            stages: saved.stages,
+        })
+    }
+}
+
+impl Default for ShellDesign {
+    /// One bit of each flavour, so that no shield is out of reach.
+    fn default() -> Self {
+        let mut design = Self::empty();
+        design.set(27, Some(Bit::Lemon));
+        design.set(28, Some(Bit::PepperRed));
+        design.set(36, Some(Bit::Honey));
+        design
+    }
+}
+
+impl ShellDesign {
+    pub fn empty() -> Self {
+        Self {
+            cells: vec![None; SHELL_GRID_SIZE * SHELL_GRID_SIZE],
+            stages: Self::single_stage(),
+        }
+    }
+
+    fn single_stage() -> Vec<Stage> {
+        vec![Stage::primary(SHELL_GRID_SIZE)]
+    }
+
+    pub fn get(&self, cell: usize) -> Option<Bit> {
+        self.cells.get(cell).copied().flatten()
+    }
+
+    pub fn set(&mut self, cell: usize, bit: Option<Bit>) {
+        if let Some(slot) = self.cells.get_mut(cell) {
+            *slot = bit;
+        }
+    }
+
+    /// Take out every bit that `keep` returns false for.
+    pub fn retain_bits(&mut self, keep: impl Fn(Bit) -> bool) {
+        for cell in &mut self.cells {
+            if cell.is_some_and(|bit| !keep(bit)) {
+                *cell = None;
+            }
+        }
+    }
+
+    pub fn bits(&self) -> impl Iterator<Item = Bit> + '_ {
+        self.cells.iter().flatten().copied()
+    }
+
+    pub fn damage_mix(&self) -> DamageMix {
+        DamageMix::from_bits(self.bits())
+    }
+
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    pub fn stage_mut(&mut self, stage: usize) -> Option<&mut Stage> {
+        self.stages.get_mut(stage)
+    }
+
+    /// The stage whose rows the cell is in.
+    pub fn stage_of(&self, cell: usize) -> usize {
+        let row = cell / SHELL_GRID_SIZE;
+        let mut end = 0;
+        for (index, stage) in self.stages.iter().enumerate() {
+            end += stage.rows;
+            if row < end {
+                return index;
+            }
+        }
+        self.stages.len() - 1
+    }
+
+    /// The mix of flavours of the bits in the stage's rows.
+    pub fn stage_mix(&self, stage: usize) -> DamageMix {
+        DamageMix::from_bits(
+            self.cells
+                .iter()
+                .enumerate()
+                .filter(|&(cell, _)| self.stage_of(cell) == stage)
+                .filter_map(|(_, bit)| *bit),
+        )
+    }
+
+    /// Split the last stage's rows to make room for a new stage after it, if there's room.
+    pub fn add_stage(&mut self) {
+        if self.stages.len() >= MAX_STAGES {
+            return;
+        }
+        let Some(last) = self.stages.last_mut().filter(|last| last.rows >= 2) else {
+            return;
+        };
+        let rows = last.rows / 2;
+        last.rows -= rows;
+        self.stages.push(Stage::split(rows));
+    }
+
+    /// Give the last stage's rows back to the one before it. The first stage always stays.
+    pub fn remove_stage(&mut self) {
+        if self.stages.len() < 2 {
+            return;
+        }
+        let Some(removed) = self.stages.pop() else {
+            return;
+        };
+        if let Some(last) = self.stages.last_mut() {
+            last.rows += removed.rows;
+        }
+    }
+
+    /// Move the border between a stage and the next one down by `steps` rows, keeping at least
+    /// one row in each.
+    pub fn resize_stage(&mut self, stage: usize, steps: i32) {
+        if stage + 1 >= self.stages.len() {
+            return;
+        }
+        let rows = self.stages[stage].rows as i32;
+        let next_rows = self.stages[stage + 1].rows as i32;
+        let steps = steps.clamp(1 - rows, next_rows - 1);
+        self.stages[stage].rows = (rows + steps) as usize;
+        self.stages[stage + 1].rows = (next_rows - steps) as usize;
+    }
+}
@@ -0,0 +1,504 @@
+//! Mothership encounters. At the end of a campaign level, once it has gone on for the rules'
+//! `boss_secs`, a mothership descends into the top of the arena. Its turrets, shield generators
+//! and core are [`Uap`]s with their own hitboxes and health, so shells hit, damage and destroy
+//! them like any other UAP.
+//!
+//! The core is shielded until both generators are down, and the mothership goes into a frenzy
+//! once it's exposed. Destroying the core brings the whole ship down and clears the level.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::{
+        arena::Arena,
+        game_mode::{EndRound, GameMode},
+        launcher::{ProjectileExplosionEvent, projectile_collision},
+        score::ScoreEvent,
+        uap::{Hitbox, Shielded, UAP_FRAME_SIZE, Uap, UapAssets, uap, uap_movement},
+    },
+    game_rules::GameRules,
+    rng::{GameRng, RngStream},
+    screens::Screen,
+    sprite_atlas::SpriteAtlas,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Mothership>();
+    app.register_type::<BossPart>();
+    app.init_resource::<BossEncounter>();
+
+    app.add_systems(OnEnter(Screen::Launchpad), reset_boss_encounter);
+    app.add_systems(
+        FixedUpdate,
+        (
+            summon_mothership.run_if(resource_equals(GameMode::Campaign)),
+            move_mothership.after(uap_movement),
+            follow_mothership,
+            update_boss_phase,
+            launch_drones,
+            bring_down_mothership,
+        )
+            .chain()
+            .before(projectile_collision)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
+    );
+    app.add_systems(
+        Update,
+        update_boss_health_bar.run_if(any_with_component::<Mothership>),
+    );
+    // Scores are counted in `Update`, so the points for the mothership are in by then.
+    app.add_systems(
+        PostUpdate,
+        clear_level.run_if(in_state(Screen::Launchpad).and(not(any_with_component::<Mothership>))),
+    );
+}
+
+/// How far above the top edge of the arena the mothership starts its entrance.
+const ENTRANCE_HEIGHT: f32 = 200.0;
+
+/// How far below the top edge of the arena the mothership hovers.
+const HOVER_DEPTH: f32 = 200.0;
+
+/// How fast the mothership descends into the arena, in pixels per second.
+const ENTRANCE_SPEED: f32 = 120.0;
+
+/// How close the mothership's centre sweeps to the sides of the arena.
+const SWEEP_MARGIN: f32 = 260.0;
+
+/// How many times the size of a UAP the mothership's hull is drawn.
+const HULL_SCALE: f32 = 8.0;
+
+/// The mothership's parts and where they sit relative to its centre.
+const PARTS: [(BossPartKind, Vec2); 7] = [
+    (BossPartKind::Core, Vec2::new(0.0, -20.0)),
+    (BossPartKind::ShieldGenerator, Vec2::new(-90.0, 20.0)),
+    (BossPartKind::ShieldGenerator, Vec2::new(90.0, 20.0)),
+    (BossPartKind::Turret, Vec2::new(-200.0, -10.0)),
+    (BossPartKind::Turret, Vec2::new(200.0, -10.0)),
+    (BossPartKind::Turret, Vec2::new(-130.0, -45.0)),
+    (BossPartKind::Turret, Vec2::new(130.0, -45.0)),
+];
+
+/// Points for bringing a mothership down, on top of those for its parts, shared between the
+/// players.
+const BOSS_SCORE: usize = 10;
+
+/// The width of the progress bar widget.
+const HEALTH_BAR_WIDTH: f32 = 480.0;
+
+/// Whether this round's mothership has arrived yet.
+#[derive(Resource, Debug, Default)]
+struct BossEncounter {
+    /// How long the round has been going.
+    round_secs: f32,
+    summoned: bool,
+}
+
+/// What the mothership is up to.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+enum BossPhase {
+    /// Descending into the arena, with every part shielded.
+    Entrance,
+    /// Sweeping from side to side while its turrets launch drones.
+    Assault,
+    /// With its core exposed, sweeping and launching drones twice as fast.
+    Frenzy,
+}
+
+impl BossPhase {
+    /// How fast the mothership sweeps from side to side, in pixels per second.
+    fn sweep_speed(self) -> f32 {
+        match self {
+            Self::Entrance => 0.0,
+            Self::Assault => 60.0,
+            Self::Frenzy => 120.0,
+        }
+    }
+
+    /// Seconds between drone launches from each turret.
+    fn drone_secs(self) -> f32 {
+        match self {
+            Self::Entrance | Self::Assault => 5.0,
+            Self::Frenzy => 2.5,
+        }
+    }
+}
+
+/// The hull of a mothership. Its parts follow it around.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Mothership {
+    phase: BossPhase,
+    direction: f32,
+    /// The health of all of its parts together.
+    max_health: f32,
+    drone_timer: Timer,
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossPartKind {
+    /// Launches drones.
+    Turret,
+    /// Shields the core while it stands.
+    ShieldGenerator,
+    /// Brings the mothership down when destroyed.
+    Core,
+}
+
+impl BossPartKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Turret => "Turret",
+            Self::ShieldGenerator => "Shield Generator",
+            Self::Core => "Core",
+        }
+    }
+
+    /// The part's health as a share of the core's.
+    fn health_share(self) -> f32 {
+        match self {
+            Self::Turret => 0.3,
+            Self::ShieldGenerator => 0.5,
+            Self::Core => 1.0,
+        }
+    }
+
+    fn scale(self) -> f32 {
+        match self {
+            Self::Turret => 1.0,
+            Self::ShieldGenerator => 1.2,
+            Self::Core => 2.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Turret => Color::srgb(1.0, 0.6, 0.5),
+            Self::ShieldGenerator => Color::srgb(0.5, 0.9, 1.0),
+            Self::Core => Color::srgb(1.0, 0.9, 0.4),
+        }
+    }
+}
+
+/// A destructible part of a mothership.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct BossPart {
+    pub kind: BossPartKind,
+    mothership: Entity,
+    /// Where the part sits relative to the mothership's centre.
+    offset: Vec2,
+}
+
+#[derive(Component)]
+struct BossHealthBar;
+
+#[derive(Component)]
+struct BossHealthFill;
+
+fn reset_boss_encounter(mut encounter: ResMut<BossEncounter>) {
+    *encounter = default();
+}
+
+/// Send in the mothership once the round has gone on long enough.
+fn summon_mothership(
+    mut commands: Commands,
+    time: Res<Time>,
+    rules: Res<GameRules>,
+    arena: Res<Arena>,
+    mut encounter: ResMut<BossEncounter>,
+    uap_assets: Res<UapAssets>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
+) {
+    if encounter.summoned || rules.boss_secs <= 0.0 {
+        return;
+    }
+    encounter.round_secs += time.delta_secs();
+    if encounter.round_secs < rules.boss_secs {
+        return;
+    }
+    encounter.summoned = true;
+
+    let position = Vec2::new(0.0, arena.half_size().y + ENTRANCE_HEIGHT);
+    let max_health = PARTS
+        .iter()
+        .map(|(kind, _)| kind.health_share() * rules.boss_health)
+        .sum();
+    let (mut hull_sprite, hull_animator) = uap_assets.animated_sprite(&sprite_atlases);
+    hull_sprite.color = Color::srgb(0.35, 0.35, 0.45);
+    let mothership = commands
+        .spawn((
+            Name::new("Mothership"),
+            Mothership {
+                phase: BossPhase::Entrance,
+                direction: 1.0,
+                max_health,
+                drone_timer: Timer::from_seconds(
+                    BossPhase::Entrance.drone_secs(),
+                    TimerMode::Repeating,
+                ),
+            },
+            hull_sprite,
+            hull_animator,
+            Transform::from_translation(position.extend(-1.0))
+                .with_scale(Vec3::new(HULL_SCALE, HULL_SCALE, 1.0)),
+            StateScoped(Screen::Launchpad),
+        ))
+        .id();
+
+    for (kind, offset) in PARTS {
+        let (mut sprite, animator) = uap_assets.animated_sprite(&sprite_atlases);
+        sprite.color = kind.color();
+        commands.spawn((
+            Name::new(kind.name()),
+            Uap::boss_part(kind.health_share() * rules.boss_health),
+            BossPart {
+                kind,
+                mothership,
+                offset,
+            },
+            Hitbox(UAP_FRAME_SIZE * kind.scale()),
+            Shielded,
+            sprite,
+            animator,
+            Transform::from_translation((position + offset).extend(0.0)).with_scale(Vec3::new(
+                kind.scale(),
+                kind.scale(),
+                1.0,
+            )),
+            StateScoped(Screen::Launchpad),
+        ));
+    }
+
+    commands.spawn(boss_health_bar());
+}
+
+fn boss_health_bar() -> impl Bundle {
+    (
+        Name::new("Boss Health Bar"),
+        BossHealthBar,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(10.0),
+            left: Percent(50.0),
+            margin: UiRect::left(Px(-HEALTH_BAR_WIDTH / 2.0)),
+            width: Px(HEALTH_BAR_WIDTH),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(5.0),
+            ..default()
+        },
+        GlobalZIndex(2),
+        StateScoped(Screen::Launchpad),
+        children![
+            widget::label("Mothership"),
+            widget::progress_bar(BossHealthFill),
+        ],
+    )
+}
+
+/// Descend into the arena, then sweep from side to side.
+fn move_mothership(
+    time: Res<Time>,
+    arena: Res<Arena>,
+    mut mothership_query: Query<(&mut Mothership, &mut Transform)>,
+) {
+    let half_size = arena.half_size();
+    let hover_y = half_size.y - HOVER_DEPTH;
+    let sweep_x = (half_size.x - SWEEP_MARGIN).max(0.0);
+    for (mut mothership, mut transform) in &mut mothership_query {
+        if mothership.phase == BossPhase::Entrance {
+            transform.translation.y =
+                (transform.translation.y - ENTRANCE_SPEED * time.delta_secs()).max(hover_y);
+            continue;
+        }
+
+        transform.translation.x +=
+            mothership.phase.sweep_speed() * mothership.direction * time.delta_secs();
+        if transform.translation.x >= sweep_x && mothership.direction > 0.0 {
+            mothership.direction = -1.0;
+            transform.translation.x = sweep_x;
+        } else if transform.translation.x <= -sweep_x && mothership.direction < 0.0 {
+            mothership.direction = 1.0;
+            transform.translation.x = -sweep_x;
+        }
+    }
+}
+
+fn follow_mothership(
+    mothership_query: Query<&Transform, With<Mothership>>,
+    mut part_query: Query<(&BossPart, &mut Transform), Without<Mothership>>,
+) {
+    for (part, mut transform) in &mut part_query {
+        if let Ok(mothership_transform) = mothership_query.get(part.mothership) {
+            let position = mothership_transform.translation.xy() + part.offset;
+            transform.translation = position.extend(transform.translation.z);
+        }
+    }
+}
+
+/// Start the assault once the mothership has arrived, and the frenzy once its shield
+/// generators are down. Each lowers the shields of the parts it exposes.
+fn update_boss_phase(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    mut mothership_query: Query<(Entity, &mut Mothership, &Transform)>,
+    part_query: Query<(Entity, &BossPart)>,
+) {
+    let hover_y = arena.half_size().y - HOVER_DEPTH;
+    for (entity, mut mothership, transform) in &mut mothership_query {
+        let parts = part_query
+            .iter()
+            .filter(|(_, part)| part.mothership == entity);
+        match mothership.phase {
+            BossPhase::Entrance if transform.translation.y <= hover_y => {
+                mothership.phase = BossPhase::Assault;
+                for (part_entity, part) in parts {
+                    if part.kind != BossPartKind::Core {
+                        commands.entity(part_entity).remove::<Shielded>();
+                    }
+                }
+            }
+            BossPhase::Assault => {
+                let parts = parts.collect::<Vec<_>>();
+                if parts
+                    .iter()
+                    .all(|(_, part)| part.kind != BossPartKind::ShieldGenerator)
+                {
+                    mothership.phase = BossPhase::Frenzy;
+                    for (part_entity, part) in parts {
+                        if part.kind == BossPartKind::Core {
+                            commands.entity(part_entity).remove::<Shielded>();
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Launch a drone from every turret now and then, up to the rules' UAP limit.
+fn launch_drones(
+    mut commands: Commands,
+    time: Res<Time>,
+    rules: Res<GameRules>,
+    mut mothership_query: Query<(Entity, &mut Mothership)>,
+    part_query: Query<(&BossPart, &Transform)>,
+    drone_query: Query<(), (With<Uap>, Without<BossPart>)>,
+    uap_assets: Res<UapAssets>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let mut drones = drone_query.iter().len();
+    for (entity, mut mothership) in &mut mothership_query {
+        if mothership.phase == BossPhase::Entrance {
+            continue;
+        }
+        let interval = Duration::from_secs_f32(mothership.phase.drone_secs());
+        if mothership.drone_timer.duration() != interval {
+            mothership.drone_timer.set_duration(interval);
+        }
+        mothership.drone_timer.tick(time.delta());
+        if !mothership.drone_timer.just_finished() {
+            continue;
+        }
+
+        let turrets = part_query
+            .iter()
+            .filter(|(part, _)| part.mothership == entity && part.kind == BossPartKind::Turret);
+        for (_, transform) in turrets {
+            if drones >= rules.max_uaps {
+                return;
+            }
+            drones += 1;
+            let rng = rng.stream(RngStream::Spawning);
+            commands
+                .spawn((
+                    uap(
+                        400.0,
+                        Uap::regular(&rules, rng),
+                        &uap_assets,
+                        &sprite_atlases,
+                        rng,
+                    ),
+                    StateScoped(Screen::Launchpad),
+                ))
+                .insert(Transform::from_translation(
+                    transform.translation.xy().extend(0.0),
+                ));
+        }
+    }
+}
+
+/// Blow up the rest of a mothership once its core is destroyed.
+fn bring_down_mothership(
+    mut commands: Commands,
+    mothership_query: Query<(Entity, &Transform), With<Mothership>>,
+    part_query: Query<(Entity, &BossPart, &Transform)>,
+    health_bar_query: Query<Entity, With<BossHealthBar>>,
+    mut explosion_events: EventWriter<ProjectileExplosionEvent>,
+    mut score_events: EventWriter<ScoreEvent>,
+) {
+    for (entity, transform) in &mothership_query {
+        let parts = part_query
+            .iter()
+            .filter(|(_, part, _)| part.mothership == entity)
+            .collect::<Vec<_>>();
+        if parts
+            .iter()
+            .any(|(_, part, _)| part.kind == BossPartKind::Core)
+        {
+            continue;
+        }
+
+        for (part_entity, _, part_transform) in parts {
+            explosion_events.write(ProjectileExplosionEvent {
+                position: part_transform.translation,
+                burst_size: 2.0,
+                color: None,
+            });
+            commands.entity(part_entity).despawn();
+        }
+        explosion_events.write(ProjectileExplosionEvent {
+            position: transform.translation.xy().extend(0.0),
+            burst_size: 3.0,
+            color: None,
+        });
+        commands.entity(entity).despawn();
+        for health_bar in &health_bar_query {
+            commands.entity(health_bar).despawn();
+        }
+        score_events.write(ScoreEvent {
+            score_to_add: BOSS_SCORE,
+            player: None,
+        });
+    }
+}
+
+/// End the level once its mothership is down.
+fn clear_level(mut commands: Commands, encounter: Res<BossEncounter>) {
+    if encounter.summoned {
+        commands.queue(EndRound {
+            reason: "Level cleared",
+        });
+    }
+}
+
+fn update_boss_health_bar(
+    mothership: Single<&Mothership>,
+    part_query: Query<&Uap, With<BossPart>>,
+    mut fill_query: Query<&mut Node, With<BossHealthFill>>,
+) {
+    let health = part_query.iter().map(Uap::health).sum::<f32>();
+    let fraction = (health / mothership.max_health).clamp(0.0, 1.0);
+    for mut node in &mut fill_query {
+        node.width = Percent(fraction * 100.0);
+    }
+}
@@ -0,0 +1,92 @@
+//! Two players sharing the launchpad, each operating their own launchers and firing shells in
+//! their own colour, picked in the workshop.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CoopMode>();
+    app.register_type::<Owner>();
+    app.init_resource::<CoopMode>();
+}
+
+/// One of the players at the keyboard.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PlayerId {
+    #[default]
+    One,
+    Two,
+}
+
+impl PlayerId {
+    pub const ALL: [Self; 2] = [Self::One, Self::Two];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::One => "Player 1",
+            Self::Two => "Player 2",
+        }
+    }
+
+    /// The colour of the player's shells and launchers.
+    pub fn color(self) -> Color {
+        match self {
+            Self::One => Color::srgb(0.5, 0.8, 1.0),
+            Self::Two => Color::srgb(1.0, 0.7, 0.4),
+        }
+    }
+}
+
+/// The player operating a launcher.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component)]
+pub struct Owner(pub PlayerId);
+
+/// Whether a second player joins in, and whether the players score together or against each
+/// other.
+#[derive(
+    Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+#[reflect(Resource)]
+pub enum CoopMode {
+    #[default]
+    Solo,
+    /// Both players' points go towards one score.
+    Shared,
+    /// Both players' points count towards the round's score, but the one who scored more wins.
+    Competitive,
+}
+
+impl CoopMode {
+    pub const ALL: [Self; 3] = [Self::Solo, Self::Shared, Self::Competitive];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Solo => "Solo",
+            Self::Shared => "Co-op",
+            Self::Competitive => "Versus",
+        }
+    }
+
+    /// The players on the launchpad.
+    pub fn players(self) -> &'static [PlayerId] {
+        match self {
+            Self::Solo => &PlayerId::ALL[..1],
+            Self::Shared | Self::Competitive => &PlayerId::ALL,
+        }
+    }
+}
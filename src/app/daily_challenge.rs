@@ -0,0 +1,213 @@
+//! The daily challenge: a round built from the date, the same for everyone playing that day.
+//!
+//! The day decides the seed of every launchpad round and a [`RulesModifier`], played as an
+//! endless round on the Normal preset by a player on their own. The best score of each day is
+//! saved, so players can compare their attempts.
+//!
+//! Only one bit of each flavour, also picked by the day, can be packed into shells. Bits the
+//! player's design has beyond those are left out for the challenge and put back after it.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use rand::{SeedableRng, rngs::StdRng, seq::IteratorRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        bits::{Bit, DamageTag, ShellDesign},
+        coop::CoopMode,
+        game_mode::GameMode,
+        score::Scoreboard,
+    },
+    game_rules::{Difficulty, RulesModifier},
+    persistence,
+    rng::{Date, RoundSeed},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DailyChallenge>();
+    app.register_type::<DailyScores>();
+    app.insert_resource(persistence::load::<DailyScores>(SCORES_FILE).unwrap_or_default());
+
+    app.add_systems(OnEnter(Screen::Title), end_daily_challenge);
+    app.add_systems(
+        Update,
+        track_score.run_if(in_state(Screen::Launchpad).and(resource_exists::<DailyChallenge>)),
+    );
+    app.add_systems(
+        OnExit(Screen::Launchpad),
+        record_best_score.run_if(resource_exists::<DailyChallenge>),
+    );
+}
+
+const SCORES_FILE: &str = "daily_scores.ron";
+
+/// The modifiers a day's challenge is picked from.
+const MODIFIERS: [RulesModifier; 4] = [
+    RulesModifier::LowGravity,
+    RulesModifier::DoubleSpeedUaps,
+    RulesModifier::ToughSwarm,
+    RulesModifier::RapidSpawns,
+];
+
+/// The challenge being played. Only present from picking it in the main menu until returning
+/// to the title screen.
+#[derive(Resource, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct DailyChallenge {
+    pub date: Date,
+    pub modifier: RulesModifier,
+    /// The bits that can be packed into shells.
+    pub bits: Vec<Bit>,
+    /// The score of the current attempt.
+    score: usize,
+    /// What to go back to once the challenge is over.
+    previous: Settings,
+}
+
+/// The settings a daily challenge overrides.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+struct Settings {
+    seed: RoundSeed,
+    difficulty: Difficulty,
+    mode: GameMode,
+    coop: CoopMode,
+    design: ShellDesign,
+}
+
+impl Settings {
+    fn read(world: &World) -> Self {
+        Self {
+            seed: *world.resource::<RoundSeed>(),
+            difficulty: *world.resource::<Difficulty>(),
+            mode: *world.resource::<GameMode>(),
+            coop: *world.resource::<CoopMode>(),
+            design: world.resource::<ShellDesign>().clone(),
+        }
+    }
+}
+
+impl DailyChallenge {
+    /// The modifier of the given day's challenge.
+    pub fn modifier_for(date: Date) -> RulesModifier {
+        MODIFIERS[(date.seed() % MODIFIERS.len() as u64) as usize]
+    }
+
+    /// The bits of the given day's challenge: one of each flavour, so that no shield is out of
+    /// reach.
+    pub fn bits_for(date: Date) -> Vec<Bit> {
+        let mut rng = StdRng::seed_from_u64(date.seed());
+        DamageTag::ALL
+            .into_iter()
+            .filter_map(|tag| {
+                Bit::ALL
+                    .into_iter()
+                    .filter(|bit| bit.tag() == tag)
+                    .choose(&mut rng)
+            })
+            .collect()
+    }
+}
+
+/// Start the challenge of the given day, to be played from the workshop.
+pub struct StartDailyChallenge(pub Date);
+
+impl Command for StartDailyChallenge {
+    fn apply(self, world: &mut World) {
+        let date = self.0;
+        let modifier = DailyChallenge::modifier_for(date);
+        let bits = DailyChallenge::bits_for(date);
+        // Starting over keeps what was there before the first challenge.
+        let previous = match world.get_resource::<DailyChallenge>() {
+            Some(challenge) => challenge.previous.clone(),
+            None => Settings::read(world),
+        };
+        world
+            .resource_mut::<ShellDesign>()
+            .retain_bits(|bit| bits.contains(&bit));
+        world.insert_resource(RoundSeed::Daily(date));
+        world.insert_resource(Difficulty::Normal);
+        world.insert_resource(GameMode::Endless);
+        world.insert_resource(CoopMode::Solo);
+        world.insert_resource(modifier);
+        world.insert_resource(DailyChallenge {
+            date,
+            modifier,
+            bits,
+            score: 0,
+            previous,
+        });
+        info!(
+            "Starting the daily challenge of {date}: {}",
+            modifier.name()
+        );
+    }
+}
+
+/// The best score of every day's challenge, by date.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[reflect(Resource)]
+pub struct DailyScores {
+    best: BTreeMap<String, usize>,
+}
+
+impl DailyScores {
+    pub fn best(&self, date: Date) -> Option<usize> {
+        self.best.get(&date.to_string()).copied()
+    }
+
+    /// The best scores of the latest days, most recent first.
+    pub fn recent(&self, days: usize) -> impl Iterator<Item = (&str, usize)> {
+        self.best
+            .iter()
+            .rev()
+            .take(days)
+            .map(|(date, score)| (date.as_str(), *score))
+    }
+
+    /// Record a score, returning whether it's the day's new best.
+    fn record(&mut self, date: Date, score: usize) -> bool {
+        let best = self.best.entry(date.to_string()).or_default();
+        let is_best = score > *best;
+        *best = (*best).max(score);
+        is_best
+    }
+}
+
+fn track_score(scoreboard: Single<&Scoreboard>, mut challenge: ResMut<DailyChallenge>) {
+    challenge.score = scoreboard.score();
+}
+
+fn record_best_score(challenge: Res<DailyChallenge>, mut scores: ResMut<DailyScores>) {
+    if scores.record(challenge.date, challenge.score) {
+        info!(
+            "New best score for the daily challenge of {}: {}",
+            challenge.date, challenge.score
+        );
+        persistence::save(SCORES_FILE, &*scores);
+    }
+}
+
+fn end_daily_challenge(
+    mut commands: Commands,
+    challenge: Option<Res<DailyChallenge>>,
+    mut round_seed: ResMut<RoundSeed>,
+    mut difficulty: ResMut<Difficulty>,
+    mut mode: ResMut<GameMode>,
+    mut coop: ResMut<CoopMode>,
+    mut modifier: ResMut<RulesModifier>,
+    mut design: ResMut<ShellDesign>,
+) {
+    let Some(challenge) = challenge else {
+        return;
+    };
+    *round_seed = challenge.previous.seed;
+    *difficulty = challenge.previous.difficulty;
+    *mode = challenge.previous.mode;
+    *coop = challenge.previous.coop;
+    *modifier = RulesModifier::None;
+    *design = challenge.previous.design.clone();
+    commands.remove_resource::<DailyChallenge>();
+}
@@ -0,0 +1,126 @@
+//! Endless rounds: UAPs drift up or down as they fly, and every one that escapes off the top or
+//! bottom of the arena costs a life. The round ends with the last life.
+//!
+//! The UAP spawner ramps up endless rounds the longer they last.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::{
+        arena::Arena,
+        boss::BossPart,
+        game_mode::{EndRound, GameMode},
+        uap::{Uap, uap_movement},
+    },
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Lives>();
+    app.add_event::<UapEscapedEvent>();
+
+    app.add_systems(
+        OnEnter(Screen::Launchpad),
+        start_endless_round.run_if(resource_equals(GameMode::Endless)),
+    );
+    app.add_systems(OnExit(Screen::Launchpad), end_endless_round);
+    app.add_systems(
+        FixedUpdate,
+        (escape_uaps.after(uap_movement), lose_lives)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad).and(resource_exists::<Lives>)),
+    );
+    // Scores are counted in `Update`, so the points for the last tick are in by then.
+    app.add_systems(
+        PostUpdate,
+        run_out_of_lives.run_if(in_state(Screen::Launchpad).and(resource_exists::<Lives>)),
+    );
+    app.add_systems(
+        Update,
+        update_lives_label.run_if(resource_exists_and_changed::<Lives>),
+    );
+}
+
+const STARTING_LIVES: u32 = 3;
+
+/// How far past the top or bottom edge of the arena a UAP has escaped.
+const ESCAPE_MARGIN: f32 = 64.0;
+
+/// The lives left in the endless round being played.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct Lives(pub u32);
+
+/// A UAP got away off the top or bottom of the arena.
+#[derive(Event, Debug)]
+pub struct UapEscapedEvent;
+
+#[derive(Component)]
+struct LivesLabel;
+
+fn start_endless_round(mut commands: Commands) {
+    commands.insert_resource(Lives(STARTING_LIVES));
+    commands.spawn((
+        Name::new("Lives"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(10.0),
+            left: Px(20.0),
+            ..default()
+        },
+        GlobalZIndex(2),
+        StateScoped(Screen::Launchpad),
+        children![(widget::label(lives_text(STARTING_LIVES)), LivesLabel)],
+    ));
+}
+
+fn end_endless_round(mut commands: Commands) {
+    commands.remove_resource::<Lives>();
+}
+
+fn lives_text(lives: u32) -> String {
+    format!("Lives: {lives}")
+}
+
+fn escape_uaps(
+    mut commands: Commands,
+    arena: Res<Arena>,
+    // The mothership's parts arrive from off screen and never leave.
+    uap_query: Query<(Entity, &Transform, Option<&ChildOf>), (With<Uap>, Without<BossPart>)>,
+    mut escaped_events: EventWriter<UapEscapedEvent>,
+) {
+    let limit = arena.half_size().y + ESCAPE_MARGIN;
+    for (entity, transform, child_of) in &uap_query {
+        if transform.translation.y.abs() > limit {
+            // Spawned UAPs belong to a spawner of their own, which goes with them.
+            let root = child_of.map_or(entity, ChildOf::parent);
+            commands.entity(root).despawn();
+            escaped_events.write(UapEscapedEvent);
+        }
+    }
+}
+
+fn lose_lives(mut escaped_events: EventReader<UapEscapedEvent>, mut lives: ResMut<Lives>) {
+    for _ in escaped_events.read() {
+        lives.0 = lives.0.saturating_sub(1);
+    }
+}
+
+/// End the round once the last life is lost.
+fn run_out_of_lives(mut commands: Commands, lives: Res<Lives>) {
+    if lives.0 == 0 {
+        commands.queue(EndRound {
+            reason: "Out of lives",
+        });
+    }
+}
+
+fn update_lives_label(lives: Res<Lives>, mut label_query: Query<&mut Text, With<LivesLabel>>) {
+    for mut text in &mut label_query {
+        text.0 = lives_text(lives.0);
+    }
+}
@@ -1,21 +1,77 @@
 use bevy::prelude::*;
 use bevy_enoki::prelude::*;
 
-use crate::app::launcher::ProjectileExplosionEvent;
+use crate::{
+    AppSystems, PausableSystems,
+    app::{
+        launcher::{
+            Detonated, Projectile, ProjectileExplosionEvent, cleanup_projectiles,
+            detonate_projectiles,
+        },
+        sound_effects::SoundEffectAssets,
+    },
+    rng::{GameRng, RngStream},
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(EnokiPlugin);
-    app.add_systems(Update, handle_explosions);
+    app.add_systems(Update, (handle_explosions, tint_bursts, style_trails));
+
+    app.add_observer(add_shell_trail);
+    app.add_systems(
+        FixedUpdate,
+        release_trails
+            .after(cleanup_projectiles)
+            .before(detonate_projectiles)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// The time between the trail particles of a shell without bits, in seconds.
+const TRAIL_SPAWN_SECS: f32 = 0.03;
+
+/// How much denser each bit packed into a shell makes its trail.
+const TRAIL_DENSITY_PER_BIT: f32 = 0.15;
+
+/// How far behind the shell's centre its trail starts.
+const TRAIL_OFFSET: f32 = 6.0;
+
+/// The spark trail behind a shell in flight.
+#[derive(Component)]
+struct ShellTrail;
+
+/// Recolours and thickens a trail once its spawner has its own copy of the effect.
+#[derive(Component)]
+struct TrailStyle {
+    color: Option<Color>,
+    spawn_secs: f32,
 }
 
+/// Recolours a burst once its spawner has its own copy of the effect.
+#[derive(Component)]
+struct BurstColor(Color);
+
 fn handle_explosions(
     mut commands: Commands,
     mut materials: ResMut<Assets<SpriteParticle2dMaterial>>,
     mut explosion_events: EventReader<ProjectileExplosionEvent>,
     server: Res<AssetServer>,
+    sound_effect_assets: Option<Res<SoundEffectAssets>>,
+    mut rng: ResMut<GameRng>,
 ) {
     for event in explosion_events.read() {
-        spawn_firework(&mut commands, &mut materials, event.position, &server);
+        let firework = spawn_firework(&mut commands, &mut materials, event.position, &server);
+        if let Some(color) = event.color {
+            commands.entity(firework).insert(BurstColor(color));
+        }
+        if let Some(sound_effect_assets) = &sound_effect_assets {
+            sound_effect_assets.play_explosion(
+                &mut commands,
+                event.position,
+                event.burst_size,
+                rng.stream(RngStream::Audio),
+            );
+        }
     }
 }
 
@@ -24,7 +80,7 @@ fn spawn_firework(
     materials: &mut ResMut<Assets<SpriteParticle2dMaterial>>,
     position: Vec3,
     server: &Res<AssetServer>,
-) {
+) -> Entity {
     // // minimal setup
     // // white quads with a default effect
     // cmd.spawn(
@@ -62,5 +118,90 @@ fn spawn_firework(
         OneShot::Despawn,
         ParticleEffectHandle(server.load("shaders/test_firework.particle.ron")),
         Transform::from_translation(position),
+    ))
+    .id()
+}
+
+/// Fade bursts from their colour to grey, in place of the effect's multicoloured fade.
+fn tint_bursts(
+    mut commands: Commands,
+    mut burst_query: Query<(Entity, &BurstColor, &mut ParticleEffectInstance)>,
+) {
+    for (entity, color, mut instance) in &mut burst_query {
+        let Some(effect) = instance.0.as_mut() else {
+            continue;
+        };
+        let color = color.0.to_linear();
+        let grey = LinearRgba::rgb(0.3, 0.3, 0.3);
+        effect.color_curve = Some(MultiCurve {
+            points: vec![(color, 0.0, None), (color, 0.3, None), (grey, 1.0, None)],
+        });
+        commands.entity(entity).remove::<BurstColor>();
+    }
+}
+
+/// Give every shell a trail in the colours of its bits, denser the more bits it carries.
+fn add_shell_trail(
+    trigger: Trigger<OnAdd, Projectile>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    projectile_query: Query<&Projectile>,
+) {
+    let Ok(projectile) = projectile_query.get(trigger.target()) else {
+        return;
+    };
+    let mix = projectile.mix();
+    commands.entity(trigger.target()).with_child((
+        Name::new("Shell Trail"),
+        ShellTrail,
+        TrailStyle {
+            color: mix.color(),
+            spawn_secs: TRAIL_SPAWN_SECS / (1.0 + TRAIL_DENSITY_PER_BIT * mix.total() as f32),
+        },
+        ParticleSpawner::default(),
+        ParticleEffectHandle(server.load("shaders/shell_trail.particle.ron")),
+        Transform::from_xyz(0.0, -TRAIL_OFFSET, -0.1),
     ));
 }
+
+fn style_trails(
+    mut commands: Commands,
+    mut trail_query: Query<(Entity, &TrailStyle, &mut ParticleEffectInstance)>,
+) {
+    for (entity, style, mut instance) in &mut trail_query {
+        let Some(effect) = instance.0.as_mut() else {
+            continue;
+        };
+        effect.spawn_rate = style.spawn_secs;
+        if let Some(color) = style.color {
+            let smoke = LinearRgba::new(0.5, 0.5, 0.5, 0.0);
+            effect.color_curve = Some(MultiCurve {
+                points: vec![(color.to_linear(), 0.0, None), (smoke, 1.0, None)],
+            });
+        }
+        commands.entity(entity).remove::<TrailStyle>();
+    }
+}
+
+/// Leave the trails of bursting shells behind where they burst, to fade out once their last
+/// particles have died instead of vanishing with the shell.
+fn release_trails(
+    mut commands: Commands,
+    shell_query: Query<(&Transform, &Children), With<Detonated>>,
+    mut trail_query: Query<
+        (Entity, &mut Transform, &mut ParticleSpawnerState),
+        (With<ShellTrail>, Without<Detonated>),
+    >,
+) {
+    for (shell_transform, children) in &shell_query {
+        let mut trails = trail_query.iter_many_mut(children);
+        while let Some((trail, mut transform, mut state)) = trails.fetch_next() {
+            *transform = shell_transform.mul_transform(*transform);
+            state.active = false;
+            commands
+                .entity(trail)
+                .remove::<ChildOf>()
+                .insert(OneShot::Despawn);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! The kind of round played on the launchpad, picked in the workshop.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        coop::{CoopMode, PlayerId},
+        daily_challenge::DailyChallenge,
+        high_scores::HighScores,
+        score::Scoreboard,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GameMode>();
+    app.init_resource::<GameMode>();
+}
+
+#[derive(
+    Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+#[reflect(Resource)]
+pub enum GameMode {
+    /// UAPs keep coming at the same rate until the player heads back to the workshop.
+    #[default]
+    FreePlay,
+    /// UAPs come faster and faster, and escaping ones cost lives until the last one is gone.
+    Endless,
+    /// Score as much as possible before the clock runs out.
+    TimeAttack,
+    /// A level that ends with a mothership, and is cleared by bringing it down.
+    Campaign,
+}
+
+impl GameMode {
+    pub const ALL: [Self; 4] = [
+        Self::FreePlay,
+        Self::Endless,
+        Self::TimeAttack,
+        Self::Campaign,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FreePlay => "Free play",
+            Self::Endless => "Endless",
+            Self::TimeAttack => "Time attack",
+            Self::Campaign => "Campaign",
+        }
+    }
+}
+
+/// How the last round to come to an end went, for the results screen.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct RoundResults {
+    pub mode: GameMode,
+    /// Why the round ended.
+    pub reason: &'static str,
+    pub score: usize,
+    /// Where the score placed among the mode's high scores, counting from 0.
+    pub place: Option<usize>,
+    /// What each player scored, when two played against each other.
+    pub player_scores: Option<[usize; 2]>,
+}
+
+/// End the round on the launchpad, record its score and show the results.
+pub struct EndRound {
+    pub reason: &'static str,
+}
+
+impl Command for EndRound {
+    fn apply(self, world: &mut World) {
+        let mode = *world.resource::<GameMode>();
+        let scoreboard = world.query::<&Scoreboard>().single(world).ok();
+        let score = scoreboard.map_or(0, Scoreboard::score);
+        let player_scores = (*world.resource::<CoopMode>() == CoopMode::Competitive)
+            .then(|| PlayerId::ALL.map(|player| scoreboard.map_or(0, |s| s.player_score(player))));
+        // Daily challenges keep their own scores, since their rules differ from day to day.
+        let place = if world.contains_resource::<DailyChallenge>() {
+            None
+        } else {
+            world.resource_mut::<HighScores>().record(mode, score)
+        };
+        world.insert_resource(RoundResults {
+            mode,
+            reason: self.reason,
+            score,
+            place,
+            player_scores,
+        });
+        world
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Results);
+    }
+}
@@ -0,0 +1,48 @@
+//! The best scores of each [`GameMode`] that has an end, saved between sessions.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{app::game_mode::GameMode, persistence};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HighScores>();
+    app.insert_resource(persistence::load::<HighScores>(SCORES_FILE).unwrap_or_default());
+}
+
+const SCORES_FILE: &str = "high_scores.ron";
+
+/// How many scores are kept for each mode.
+const MAX_HIGH_SCORES: usize = 10;
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[reflect(Resource)]
+pub struct HighScores {
+    scores: HashMap<GameMode, Vec<usize>>,
+}
+
+impl HighScores {
+    /// The best scores of a mode, highest first.
+    pub fn of(&self, mode: GameMode) -> &[usize] {
+        self.scores.get(&mode).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add a score to the list of its mode and save the list if it made it. Returns its place,
+    /// counting from 0. Rounds that scored nothing aren't kept.
+    pub fn record(&mut self, mode: GameMode, score: usize) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+        let scores = self.scores.entry(mode).or_default();
+        let place = scores.partition_point(|&high_score| high_score >= score);
+        if place >= MAX_HIGH_SCORES {
+            return None;
+        }
+        scores.insert(place, score);
+        scores.truncate(MAX_HIGH_SCORES);
+        persistence::save(SCORES_FILE, self);
+        Some(place)
+    }
+}
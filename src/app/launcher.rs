@@ -1,19 +1,36 @@
-use bevy::prelude::*;
+use bevy::{
+    asset::{UntypedAssetId, VisitAssetDependencies},
+    prelude::*,
+};
 
 use crate::{
+    AppSystems, PausableSystems,
     app::{
+        actions::PlayerActions,
+        bits::{DamageMix, DamageTag, ShellDesign, Stage},
+        coop::{CoopMode, Owner, PlayerId},
         score::ScoreEvent,
-        uap::{DestroyUapEvent, Uap},
+        sound_effects::SoundEffectAssets,
+        uap::{DamageSource, DestroyUapEvent, Hitbox, Shielded, Uap},
+        uap_damage::UapHitEvent,
+        upgrades::{LauncherUpgrades, Progress},
     },
+    asset_manifest::{AssetManifest, FromAssetManifest, MissingAssetKey},
     asset_tracking::LoadResource,
+    audio::AttenuationOrigin,
+    game_rules::GameRules,
+    rng::{GameRng, RngStream},
     screens::Screen,
+    sprite_atlas::SpriteAtlas,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<ProjectileExplosionEvent>();
 
     app.register_type::<Launcher>();
+    app.register_type::<ActiveLauncher>();
     app.register_type::<LauncherCrank>();
+    app.register_type::<Payload>();
 
     app.register_type::<LauncherAssets>();
     app.register_type::<ProjectileAssets>();
@@ -23,128 +40,409 @@ pub(super) fn plugin(app: &mut App) {
     app.load_resource::<ProjectileAssets>();
     app.load_resource::<LauncherCrankAssets>();
 
+    app.add_systems(OnEnter(Screen::Launchpad), spawn_launchers);
     app.add_systems(OnExit(Screen::Launchpad), despawn_launcher);
+    app.add_systems(Update, tint_launchers.run_if(in_state(Screen::Launchpad)));
     app.add_systems(
-        Update,
-        ((
+        FixedUpdate,
+        (
+            switch_launcher,
             launcher_rotation,
             launcher_shooting,
             launcher_crank_rotation,
             projectile_movement,
             projectile_collision,
             cleanup_projectiles,
+            split_payloads,
+            detonate_projectiles,
         )
             .chain()
-            .run_if(in_state(Screen::Launchpad)),),
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
     );
 }
 
 #[derive(Event)]
 pub struct ProjectileExplosionEvent {
     pub position: Vec3,
+    /// Relative size of the burst, where `1.0` is a regular shell.
+    pub burst_size: f32,
+    /// The colour of the burst, or `None` for the regular multicoloured one.
+    pub color: Option<Color>,
 }
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+/// How far apart launchers stand on the launchpad.
+const LAUNCHER_SPACING: f32 = 320.0;
+
+/// How far apart the barrels of a double-barrelled launcher are.
+const BARREL_GAP: f32 = 14.0;
+
+/// The tint of the launchers the player isn't operating.
+const INACTIVE_LAUNCHER_TINT: Color = Color::srgb(0.5, 0.5, 0.5);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
 #[reflect(Component)]
-struct Launcher {
-    rotation_speed: i32,
-    projectile_speed: i32,
+pub struct Launcher {
     height: i32,
+    pub upgrades: LauncherUpgrades,
+    /// The seconds left before the launcher can fire again.
+    reload_secs: f32,
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        Self {
+            height: 40,
+            upgrades: default(),
+            reload_secs: 0.0,
+        }
+    }
 }
 
+impl Launcher {
+    fn new(upgrades: LauncherUpgrades) -> Self {
+        Self {
+            upgrades,
+            ..default()
+        }
+    }
+
+    /// Where each of the launcher's barrels fires a shell from, and the direction they fly in.
+    fn muzzles(&self, transform: &Transform) -> impl Iterator<Item = (Vec3, Vec2)> {
+        let (position, direction) = self.muzzle(transform);
+        let across = transform.rotation * Vec3::X;
+        let barrels = self.upgrades.barrels();
+        (0..barrels).map(move |barrel| {
+            let offset = (barrel as f32 - (barrels - 1) as f32 / 2.0) * BARREL_GAP;
+            (position + across * offset, direction)
+        })
+    }
+
+    /// Whether the launcher is ready to fire again.
+    pub(super) fn reloaded(&self) -> bool {
+        self.reload_secs <= 0.0
+    }
+
+    /// Where shells leave the launcher and the direction they fly in.
+    pub(super) fn muzzle(&self, transform: &Transform) -> (Vec3, Vec2) {
+        let direction = transform.rotation * Vec3::Y;
+        let position = transform.translation + direction * self.height as f32;
+        (position, direction.xy().normalize())
+    }
+}
+
+/// How many times faster than the launcher its crank turns.
+const CRANK_RATIO: f32 = 3.0;
+
+/// The launcher the player is operating.
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
+pub struct ActiveLauncher;
+
+/// The crank of a launcher, which turns while the launcher does.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
 struct LauncherCrank {
-    rotation_speed: i32,
+    launcher: Entity,
 }
 
+/// How far a shell flies before it bursts on its own.
+const MAX_DISTANCE: f32 = 1000.0;
+
+/// The size of a shell fired from the launcher.
+const SHELL_SIZE: f32 = 12.0;
+
+/// The size of the shells a shell bursts into.
+const SUB_SHELL_SIZE: f32 = 8.0;
+
+/// How much of a shell's damage each of the shells it bursts into does.
+const SUB_SHELL_DAMAGE: f32 = 0.5;
+
+/// How big the bursts of the shells a shell bursts into are, relative to a shell packed with the
+/// same bits.
+const SUB_SHELL_BURST_SIZE: f32 = 0.6;
+
+/// How big the burst of a shell without any bits is.
+const EMPTY_BURST_SIZE: f32 = 0.6;
+
+/// How much bigger each bit packed into a shell makes its burst.
+const BURST_SIZE_PER_BIT: f32 = 0.05;
+
+/// The biggest burst a shell can have, however many bits it's packed with.
+const MAX_BURST_SIZE: f32 = 2.5;
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
 #[reflect(Component)]
-struct Projectile {
+pub struct Projectile {
     velocity: Vec2,
     distance: f32,
     damage: f32,
+    mix: DamageMix,
+    burst_size: f32,
+    /// The seconds left before the shell bursts on its own, if it has a fuse.
+    fuse_secs: Option<f32>,
+    /// The player who fired the shell, who scores what it brings down.
+    owner: PlayerId,
+}
+
+impl Projectile {
+    /// A regular shell fired in the given direction.
+    pub(super) fn new(direction: Vec2, rules: &GameRules) -> Self {
+        Self {
+            velocity: direction * rules.projectile_speed,
+            distance: 0.,
+            damage: rules.projectile_damage,
+            mix: DamageMix::default(),
+            burst_size: EMPTY_BURST_SIZE,
+            fuse_secs: None,
+            owner: PlayerId::One,
+        }
+    }
+
+    fn with_owner(mut self, owner: PlayerId) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Speed the shell up by the given factor.
+    fn with_speed_scale(mut self, scale: f32) -> Self {
+        self.velocity *= scale;
+        self
+    }
+
+    /// Make the shell burst on its own after the given time, unless it's 0.
+    pub(super) fn with_fuse(mut self, secs: f32) -> Self {
+        self.fuse_secs = (secs > 0.0).then_some(secs);
+        self
+    }
+
+    /// Pack the shell with bits of the given mix of flavours. The more bits, the bigger the
+    /// burst.
+    pub(super) fn with_mix(mut self, mix: DamageMix) -> Self {
+        self.mix = mix;
+        self.burst_size =
+            (EMPTY_BURST_SIZE + BURST_SIZE_PER_BIT * mix.total() as f32).min(MAX_BURST_SIZE);
+        self
+    }
+
+    pub(super) fn mix(&self) -> DamageMix {
+        self.mix
+    }
+
+    /// The colour of the shell's burst, taken from its main flavour.
+    fn burst_color(&self) -> Option<Color> {
+        self.mix.dominant().map(DamageTag::color)
+    }
+}
+
+/// The stages a shell bursts into when it detonates, each splitting from the one before.
+#[derive(Component, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Payload(Vec<PayloadStage>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+struct PayloadStage {
+    count: u32,
+    /// The angle the shells are fanned out over, in radians.
+    spread: f32,
+    fuse_secs: f32,
+    mix: DamageMix,
 }
 
-pub fn launcher(
+impl Payload {
+    /// The stages of the design after the first, which is the shell fired from the launcher.
+    pub(super) fn from_design(design: &ShellDesign) -> Self {
+        Self(
+            design
+                .stages()
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(
+                    |(
+                        index,
+                        &Stage {
+                            count,
+                            spread_degrees,
+                            fuse_secs,
+                            ..
+                        },
+                    )| PayloadStage {
+                        count,
+                        spread: spread_degrees.to_radians(),
+                        fuse_secs,
+                        mix: design.stage_mix(index),
+                    },
+                )
+                .collect(),
+        )
+    }
+}
+
+/// A shell that has burst this update, to be removed once its payload is out.
+#[derive(Component)]
+pub(super) struct Detonated;
+
+fn shell_sprite(projectile_assets: &ProjectileAssets, atlas: &SpriteAtlas, size: f32) -> Sprite {
+    Sprite {
+        image: projectile_assets.shell.clone(),
+        texture_atlas: Some(atlas.texture_atlas(0)),
+        custom_size: Some(Vec2::splat(size)),
+        ..default()
+    }
+}
+
+/// Put up the launchers the rules call for, with the upgrades bought in the workshop and at
+/// least one for each player. A player on their own starts at the one in the middle of the
+/// launchpad with the rest either side of it in turn, while two players take a side each.
+pub fn spawn_launchers(
+    mut commands: Commands,
+    launcher_assets: Res<LauncherAssets>,
+    launcher_crank_assets: Res<LauncherCrankAssets>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
+    rules: Res<GameRules>,
+    coop: Res<CoopMode>,
+    progress: Res<Progress>,
+) {
+    let players = coop.players();
+    let count = rules.launchers.max(players.len());
+    let owner = |index: usize| players[index * players.len() / count];
+    for index in 0..count {
+        let x = match players.len() {
+            1 => {
+                let side = if index % 2 == 1 { -1.0 } else { 1.0 };
+                side * LAUNCHER_SPACING * index.div_ceil(2) as f32
+            }
+            _ => (index as f32 - (count - 1) as f32 / 2.0) * LAUNCHER_SPACING,
+        };
+        let mut launcher = commands.spawn((
+            launcher(&launcher_assets, &sprite_atlases, x, progress.upgrades),
+            Owner(owner(index)),
+        ));
+        if index == 0 || owner(index - 1) != owner(index) {
+            launcher.insert(ActiveLauncher);
+        }
+        let launcher = launcher.id();
+        commands.spawn(launcher_crank(
+            &launcher_crank_assets,
+            &sprite_atlases,
+            x,
+            launcher,
+        ));
+    }
+}
+
+fn launcher(
     launcher_assets: &LauncherAssets,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    sprite_atlases: &Assets<SpriteAtlas>,
+    x: f32,
+    upgrades: LauncherUpgrades,
 ) -> impl Bundle {
-    let launcher_height = 40;
-
-    let layout = TextureAtlasLayout::from_grid(UVec2::new(12, 48), 1, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let atlas = sprite_atlases
+        .get(&launcher_assets.launcher_atlas)
+        .expect("the launcher atlas is loaded with the launcher assets");
 
     (
         Sprite {
             image: launcher_assets.launcher.clone(),
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout,
-                index: 1,
-            }),
+            texture_atlas: Some(atlas.texture_atlas(0)),
             anchor: bevy::sprite::Anchor::Custom(Vec2::new(0.0, -0.35)),
             ..default()
         },
-        Transform::from_translation(Vec3::new(0.0, -450.0, 0.0)),
-        Launcher {
-            rotation_speed: 2,
-            projectile_speed: 500,
-            height: launcher_height,
-        },
+        Transform::from_translation(Vec3::new(x, -450.0, 0.0)),
+        Launcher::new(upgrades),
+        // Sounds are heard relative to the nearest launcher.
+        AttenuationOrigin,
     )
 }
 
-pub fn launcher_crank(
+fn launcher_crank(
     launcher_crank_assets: &LauncherCrankAssets,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    sprite_atlases: &Assets<SpriteAtlas>,
+    x: f32,
+    launcher: Entity,
 ) -> impl Bundle {
-    let layout = TextureAtlasLayout::from_grid(UVec2::new(16, 16), 1, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let atlas = sprite_atlases
+        .get(&launcher_crank_assets.launcher_crank_atlas)
+        .expect("the launcher crank atlas is loaded with the launcher crank assets");
 
     (
         Sprite {
             image: launcher_crank_assets.launcher_crank.clone(),
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout,
-                index: 1,
-            }),
+            texture_atlas: Some(atlas.texture_atlas(0)),
             ..default()
         },
-        Transform::from_translation(Vec3::new(0.0, -450.0, 1.0)),
-        LauncherCrank { rotation_speed: 6 },
+        Transform::from_translation(Vec3::new(x, -450.0, 1.0)),
+        LauncherCrank { launcher },
     )
 }
 
-fn launcher_rotation(
-    time: Res<Time>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Transform, &Launcher)>,
+/// Hand control to the player's next launcher to the right, or back to their leftmost one.
+fn switch_launcher(
+    mut commands: Commands,
+    actions: Res<PlayerActions>,
+    launcher_query: Query<(Entity, &Transform, &Owner, Has<ActiveLauncher>), With<Launcher>>,
 ) {
-    for (mut transform, launcher) in query.iter_mut() {
-        let mut rotation_direction = 0.0;
-
-        // Check for left rotation
-        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
-            rotation_direction += 1.0;
+    for player in PlayerId::ALL {
+        if !actions.of(player).switch {
+            continue;
         }
+        let mut launchers = launcher_query
+            .iter()
+            .filter(|(_, _, owner, _)| owner.0 == player)
+            .collect::<Vec<_>>();
+        launchers.sort_by(|(_, a, ..), (_, b, ..)| a.translation.x.total_cmp(&b.translation.x));
+        let Some(active) = launchers.iter().position(|&(.., active)| active) else {
+            continue;
+        };
+        let next = (active + 1) % launchers.len();
+        commands
+            .entity(launchers[active].0)
+            .remove::<ActiveLauncher>();
+        commands.entity(launchers[next].0).insert(ActiveLauncher);
+    }
+}
 
-        // Check for right rotation
-        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
-            rotation_direction -= 1.0;
-        }
+/// Grey out the launchers nobody is operating. With two players, the ones being operated take
+/// their player's colour.
+fn tint_launchers(
+    coop: Res<CoopMode>,
+    mut launcher_query: Query<(&mut Sprite, &Owner, Has<ActiveLauncher>), With<Launcher>>,
+) {
+    for (mut sprite, owner, active) in &mut launcher_query {
+        sprite.color = match (active, *coop) {
+            (false, _) => INACTIVE_LAUNCHER_TINT,
+            (true, CoopMode::Solo) => Color::WHITE,
+            (true, _) => owner.0.color(),
+        };
+    }
+}
+
+fn launcher_rotation(
+    time: Res<Time>,
+    rules: Res<GameRules>,
+    actions: Res<PlayerActions>,
+    mut query: Query<(&mut Transform, &Launcher, &Owner), With<ActiveLauncher>>,
+) {
+    for (mut transform, launcher, owner) in query.iter_mut() {
+        let max_angle = rules.launcher_max_angle + launcher.upgrades.extra_aim_angle();
+        let rotation_direction = actions.of(owner.0).turn as f32;
 
         // Apply rotation
         if rotation_direction != 0.0 {
-            let rotation_amount =
-                rotation_direction * launcher.rotation_speed as f32 * time.delta_secs();
+            let rotation_amount = rotation_direction
+                * rules.launcher_rotation_speed
+                * launcher.upgrades.rotation_speed_scale()
+                * time.delta_secs();
             transform.rotate_z(rotation_amount);
 
             // Clamp rotation to reasonable bounds
             let current_rotation = transform.rotation.to_euler(EulerRot::ZYX).0;
-            if current_rotation > 1.2 {
-                transform.rotation = Quat::from_rotation_z(1.2);
-            } else if current_rotation < -1.2 {
-                transform.rotation = Quat::from_rotation_z(-1.2);
+            if current_rotation > max_angle {
+                transform.rotation = Quat::from_rotation_z(max_angle);
+            } else if current_rotation < -max_angle {
+                transform.rotation = Quat::from_rotation_z(-max_angle);
             }
         }
     }
@@ -152,34 +450,36 @@ fn launcher_rotation(
 
 fn launcher_crank_rotation(
     time: Res<Time>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    rules: Res<GameRules>,
+    actions: Res<PlayerActions>,
     mut query: Query<(&mut Transform, &LauncherCrank)>,
+    launcher_query: Query<(&Launcher, &Owner), With<ActiveLauncher>>,
 ) {
-    for (mut transform, launcher_crank) in query.iter_mut() {
-        let mut rotation_direction = 0.0;
-
-        // Check for right rotation; rotate opposite of the launcher
-        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
-            rotation_direction -= 1.0;
-        }
-
-        // Check for left rotation; rotate opposite of the launcher
-        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
-            rotation_direction += 1.0;
-        }
+    for (mut transform, crank) in query.iter_mut() {
+        // Only the cranks of the launchers being operated turn.
+        let Ok((launcher, owner)) = launcher_query.get(crank.launcher) else {
+            continue;
+        };
+        let max_angle =
+            (rules.launcher_max_angle + launcher.upgrades.extra_aim_angle()) * CRANK_RATIO;
+        // Rotate opposite of the launcher
+        let rotation_direction = -actions.of(owner.0).turn as f32;
 
         // Apply rotation
         if rotation_direction != 0.0 {
-            let rotation_amount =
-                rotation_direction * launcher_crank.rotation_speed as f32 * time.delta_secs();
+            let rotation_amount = rotation_direction
+                * rules.launcher_rotation_speed
+                * launcher.upgrades.rotation_speed_scale()
+                * CRANK_RATIO
+                * time.delta_secs();
             transform.rotate_z(rotation_amount);
 
             // Ideally only rotate the crank while the launcher is rotating
             let current_rotation = transform.rotation.to_euler(EulerRot::ZYX).0;
-            if current_rotation > 3.6 {
-                transform.rotation = Quat::from_rotation_z(3.6);
-            } else if current_rotation < -3.6 {
-                transform.rotation = Quat::from_rotation_z(-3.6);
+            if current_rotation > max_angle {
+                transform.rotation = Quat::from_rotation_z(max_angle);
+            } else if current_rotation < -max_angle {
+                transform.rotation = Quat::from_rotation_z(-max_angle);
             }
         }
     }
@@ -187,44 +487,66 @@ fn launcher_crank_rotation(
 
 fn launcher_shooting(
     mut commands: Commands,
+    time: Res<Time>,
     projectile_assets: Res<ProjectileAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    launcher_query: Query<(&Transform, &Launcher)>,
+    sound_effect_assets: Res<SoundEffectAssets>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
+    rules: Res<GameRules>,
+    design: Res<ShellDesign>,
+    coop: Res<CoopMode>,
+    actions: Res<PlayerActions>,
+    mut launcher_query: Query<(&Transform, &mut Launcher, &Owner, Has<ActiveLauncher>)>,
+    mut rng: ResMut<GameRng>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Space) {
-        let layout = TextureAtlasLayout::from_grid(UVec2::new(12, 12), 1, 1, None, None);
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
-        for (launcher_transform, launcher) in launcher_query.iter() {
-            let rotation = launcher_transform.rotation;
-            let direction = rotation * Vec3::Y;
-            let direction_2d = Vec2::new(direction.x, direction.y).normalize();
-            let spawn_offset = direction * launcher.height as f32;
-            let spawn_position = launcher_transform.translation + spawn_offset;
-
-            commands.spawn((
-                Sprite {
-                    image: projectile_assets.shell.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: 1,
-                    }),
-                    custom_size: Some(Vec2::new(12.0, 12.0)),
-                    ..default()
-                },
-                Transform::from_translation(spawn_position).with_rotation(rotation),
-                Projectile {
-                    velocity: direction_2d * launcher.projectile_speed as f32,
-                    distance: 0.,
-                    damage: 20.0,
-                },
-            ));
+    for (_, mut launcher, ..) in &mut launcher_query {
+        launcher.reload_secs = (launcher.reload_secs - time.delta_secs()).max(0.0);
+    }
+    if actions.0.iter().any(|actions| actions.fire) {
+        let Some(atlas) = sprite_atlases.get(&projectile_assets.shell_atlas) else {
+            return;
+        };
+
+        for (launcher_transform, mut launcher, owner, active) in launcher_query.iter_mut() {
+            if !active || !actions.of(owner.0).fire || !launcher.reloaded() {
+                continue;
+            }
+            launcher.reload_secs = rules.launcher_reload_secs * launcher.upgrades.reload_scale();
+
+            let primary = design.stages()[0];
+            for (spawn_position, direction) in launcher.muzzles(launcher_transform) {
+                let mut sprite = shell_sprite(&projectile_assets, atlas, SHELL_SIZE);
+                // Players sharing the launchpad tell their shells apart by colour.
+                if *coop != CoopMode::Solo {
+                    sprite.color = owner.0.color();
+                }
+                let mut shell = commands.spawn((
+                    sprite,
+                    Transform::from_translation(spawn_position)
+                        .with_rotation(launcher_transform.rotation),
+                    Projectile::new(direction, &rules)
+                        .with_owner(owner.0)
+                        .with_speed_scale(launcher.upgrades.muzzle_speed_scale())
+                        .with_mix(design.stage_mix(0))
+                        .with_fuse(primary.fuse_secs),
+                ));
+                if design.stages().len() > 1 {
+                    shell.insert(Payload::from_design(&design));
+                }
+            }
+            let (spawn_position, _) = launcher.muzzle(launcher_transform);
+            sound_effect_assets.play_launch(
+                &mut commands,
+                spawn_position,
+                rng.stream(RngStream::Audio),
+            );
         }
     }
 }
 
-fn projectile_movement(time: Res<Time>, mut query: Query<(&mut Transform, &mut Projectile)>) {
+pub(super) fn projectile_movement(
+    time: Res<Time>,
+    mut query: Query<(&mut Transform, &mut Projectile)>,
+) {
     for (mut transform, mut projectile) in query.iter_mut() {
         let movement = Vec2::new(
             projectile.velocity.x * time.delta_secs(),
@@ -235,25 +557,39 @@ fn projectile_movement(time: Res<Time>, mut query: Query<(&mut Transform, &mut P
         transform.translation.y += movement.y;
 
         projectile.distance += movement.length();
+        if let Some(fuse_secs) = &mut projectile.fuse_secs {
+            *fuse_secs -= time.delta_secs();
+        }
     }
 }
 
-fn projectile_collision(
+pub(super) fn projectile_collision(
     mut commands: Commands,
-    mut explosion_events: EventWriter<ProjectileExplosionEvent>,
     mut destroy_uap_events: EventWriter<DestroyUapEvent>,
     mut score_events: EventWriter<ScoreEvent>,
+    mut hit_events: EventWriter<UapHitEvent>,
     projectiles: Query<(Entity, &Transform, &Projectile, &Sprite)>,
-    mut uaps: Query<(Entity, &Transform, &mut Uap, &Sprite)>,
+    mut uaps: Query<(
+        Entity,
+        &Transform,
+        &mut Uap,
+        &Sprite,
+        Option<&Hitbox>,
+        Has<Shielded>,
+    )>,
 ) {
     for (projectile_entity, projectile_transform, projectile, projectile_sprite) in
         projectiles.iter()
     {
-        for (uap_entity, uap_transform, mut uap, uap_sprite) in uaps.iter_mut() {
+        for (uap_entity, uap_transform, mut uap, uap_sprite, hitbox, shielded) in uaps.iter_mut() {
+            // Shells hitting together only destroy the UAP once, and the rest fly on past it.
+            if uap.health() <= 0.0 {
+                continue;
+            }
             let projectile_size = projectile_sprite
                 .custom_size
                 .unwrap_or(Vec2::new(12.0, 12.0));
-            let uap_size = uap_sprite.custom_size.unwrap_or(Vec2::new(56.0, 24.0));
+            let uap_size = Hitbox::size_of(hitbox, uap_sprite);
             let projectile_position = projectile_transform.translation.xy();
             let uap_position = uap_transform.translation.xy();
             let projectile_half = projectile_size / 2.0;
@@ -264,17 +600,30 @@ fn projectile_collision(
                 (projectile_position.y - uap_position.y).abs() < (projectile_half.y + uap_half.y);
 
             if x_overlap && y_overlap {
-                explosion_events.write(ProjectileExplosionEvent {
-                    position: projectile_transform.translation,
+                commands.entity(projectile_entity).insert(Detonated);
+                let Some(damage) =
+                    uap.damage_through_defences(projectile.damage, &projectile.mix, shielded)
+                else {
+                    break;
+                };
+
+                hit_events.write(UapHitEvent {
+                    entity: uap_entity,
+                    direction: (uap_position - projectile_position)
+                        .try_normalize()
+                        .unwrap_or(projectile.velocity),
                 });
                 uap.take_damage(
-                    projectile.damage,
+                    damage,
+                    DamageSource {
+                        player: projectile.owner,
+                        mix: projectile.mix,
+                    },
                     uap_entity,
                     uap_transform,
                     &mut destroy_uap_events,
                     &mut score_events,
                 );
-                commands.entity(projectile_entity).despawn();
 
                 break;
             }
@@ -282,73 +631,153 @@ fn projectile_collision(
     }
 }
 
-fn cleanup_projectiles(
-    mut commands: Commands,
-    mut explosion_events: EventWriter<ProjectileExplosionEvent>,
-    query: Query<(Entity, &Transform, &Projectile)>,
-) {
-    for (entity, transform, projectile) in query.iter() {
-        let auto_detonate = projectile.distance > 1000.0;
+pub(super) fn cleanup_projectiles(mut commands: Commands, query: Query<(Entity, &Projectile)>) {
+    for (entity, projectile) in query.iter() {
+        let auto_detonate = projectile.distance > MAX_DISTANCE
+            || projectile.fuse_secs.is_some_and(|secs| secs <= 0.0);
 
         if auto_detonate {
-            explosion_events.write(ProjectileExplosionEvent {
-                position: transform.translation,
-            });
-            commands.entity(entity).despawn();
+            commands.entity(entity).insert(Detonated);
+        }
+    }
+}
+
+/// Fan the next stage of each burst shell's payload out from where it burst, carrying on in the
+/// direction it flew, with whatever stages are left for them to burst into.
+fn split_payloads(
+    mut commands: Commands,
+    projectile_assets: Res<ProjectileAssets>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
+    query: Query<(&Transform, &Projectile, &Payload, &Sprite), With<Detonated>>,
+) {
+    let Some(atlas) = sprite_atlases.get(&projectile_assets.shell_atlas) else {
+        return;
+    };
+    for (transform, projectile, payload, sprite) in &query {
+        let Some((stage, rest)) = payload.0.split_first() else {
+            continue;
+        };
+        for index in 0..stage.count {
+            let angle = match stage.count {
+                1 => 0.0,
+                count => stage.spread * (index as f32 / (count - 1) as f32 - 0.5),
+            };
+            let velocity = Vec2::from_angle(angle).rotate(projectile.velocity);
+            let mut sub_shell = Projectile {
+                velocity,
+                damage: projectile.damage * SUB_SHELL_DAMAGE,
+                owner: projectile.owner,
+                ..default()
+            }
+            .with_mix(stage.mix)
+            .with_fuse(stage.fuse_secs);
+            sub_shell.burst_size *= SUB_SHELL_BURST_SIZE;
+            let mut shell = commands.spawn((
+                Name::new("Sub-shell"),
+                Sprite {
+                    color: sprite.color,
+                    ..shell_sprite(&projectile_assets, atlas, SUB_SHELL_SIZE)
+                },
+                transform.with_rotation(transform.rotation * Quat::from_rotation_z(angle)),
+                sub_shell,
+            ));
+            if !rest.is_empty() {
+                shell.insert(Payload(rest.to_vec()));
+            }
         }
     }
 }
 
-fn despawn_launcher(mut commands: Commands, query: Query<Entity, With<Launcher>>) {
+/// Burst the shells that hit something, flew too far or ran out of fuse.
+pub(super) fn detonate_projectiles(
+    mut commands: Commands,
+    mut explosion_events: EventWriter<ProjectileExplosionEvent>,
+    query: Query<(Entity, &Transform, &Projectile), With<Detonated>>,
+) {
+    for (entity, transform, projectile) in &query {
+        explosion_events.write(ProjectileExplosionEvent {
+            position: transform.translation,
+            burst_size: projectile.burst_size,
+            color: projectile.burst_color(),
+        });
+        commands.entity(entity).despawn();
+    }
+}
+
+fn despawn_launcher(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<Launcher>, With<LauncherCrank>)>>,
+) {
     for entity in query.iter() {
         commands.entity(entity).despawn();
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LauncherAssets {
-    #[dependency]
     launcher: Handle<Image>,
+    launcher_atlas: Handle<SpriteAtlas>,
 }
 
-impl FromWorld for LauncherAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            launcher: assets.load("images/launcher.png"),
-        }
+impl FromAssetManifest for LauncherAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            launcher: manifest.image("launcher")?,
+            launcher_atlas: manifest.atlas("launcher")?,
+        })
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
+impl VisitAssetDependencies for LauncherAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.launcher.visit_dependencies(visit);
+        self.launcher_atlas.visit_dependencies(visit);
+    }
+}
+
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct ProjectileAssets {
-    #[dependency]
     shell: Handle<Image>,
+    shell_atlas: Handle<SpriteAtlas>,
 }
 
-impl FromWorld for ProjectileAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            shell: assets.load("images/shell.png"),
-        }
+impl FromAssetManifest for ProjectileAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            shell: manifest.image("shell")?,
+            shell_atlas: manifest.atlas("shell")?,
+        })
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
+impl VisitAssetDependencies for ProjectileAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.shell.visit_dependencies(visit);
+        self.shell_atlas.visit_dependencies(visit);
+    }
+}
+
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LauncherCrankAssets {
-    #[dependency]
     launcher_crank: Handle<Image>,
+    launcher_crank_atlas: Handle<SpriteAtlas>,
 }
 
-impl FromWorld for LauncherCrankAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            launcher_crank: assets.load("images/launcher_crank.png"),
-        }
+impl FromAssetManifest for LauncherCrankAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            launcher_crank: manifest.image("launcher_crank")?,
+            launcher_crank_atlas: manifest.atlas("launcher_crank")?,
+        })
+    }
+}
+
+impl VisitAssetDependencies for LauncherCrankAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.launcher_crank.visit_dependencies(visit);
+        self.launcher_crank_atlas.visit_dependencies(visit);
     }
 }
@@ -5,28 +5,61 @@
 
 use bevy::prelude::*;
 
+mod actions;
 mod animation;
+pub mod arena;
+pub mod bits;
+pub mod boss;
+pub mod coop;
+pub mod daily_challenge;
+pub mod endless;
 mod fireworks;
+pub mod game_mode;
+pub mod high_scores;
 pub mod launcher;
 pub mod level;
-mod movement;
+pub mod movement;
 pub mod player;
+pub mod replay;
 pub mod score;
+#[cfg(feature = "simulation")]
+pub mod simulation;
+mod sound_effects;
+pub mod time_attack;
 pub mod uap;
-mod uap_animation;
-mod uap_spawner;
+mod uap_damage;
+mod uap_debris;
+pub mod uap_spawner;
+pub mod upgrades;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
+        actions::plugin,
         animation::plugin,
+        arena::plugin,
+        bits::plugin,
         launcher::plugin,
-        level::plugin,
         movement::plugin,
         player::plugin,
+        replay::plugin,
         fireworks::plugin,
         uap::plugin,
-        uap_animation::plugin,
+        uap_damage::plugin,
+        uap_debris::plugin,
         uap_spawner::plugin,
         score::plugin,
+        sound_effects::plugin,
     ));
+    // Kinds of rounds and their scores.
+    app.add_plugins((
+        boss::plugin,
+        coop::plugin,
+        daily_challenge::plugin,
+        endless::plugin,
+        game_mode::plugin,
+        high_scores::plugin,
+        time_attack::plugin,
+    ));
+    // Progress carried between rounds.
+    app.add_plugins(upgrades::plugin);
 }
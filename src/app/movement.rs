@@ -7,15 +7,15 @@
 //!   This is done in the `player` module, as it is specific to the player
 //!   character.
 //! - Apply movement based on [`MovementController`] intent and maximum speed.
-//! - Wrap the character within the window.
+//! - Wrap the character within the [`Arena`].
 //!
 //! Note that the implementation used here is limited for demonstration
 //! purposes. If you want to move the player in a smoother way,
 //! consider using a [fixed timestep](https://github.com/bevyengine/bevy/blob/main/examples/movement/physics_in_fixed_timestep.rs).
 
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
-use crate::{AppSystems, PausableSystems};
+use crate::{AppSystems, PausableSystems, app::arena::Arena};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<MovementController>();
@@ -28,6 +28,13 @@ pub(super) fn plugin(app: &mut App) {
             .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
+    // UAPs move in fixed steps, so they need to wrap between steps as well.
+    app.add_systems(
+        FixedUpdate,
+        apply_screen_wrap
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
 }
 
 /// These are the movement parameters for our character controller.
@@ -68,11 +75,8 @@ fn apply_movement(
 #[reflect(Component)]
 pub struct ScreenWrap;
 
-fn apply_screen_wrap(
-    window: Single<&Window, With<PrimaryWindow>>,
-    mut wrap_query: Query<&mut Transform, With<ScreenWrap>>,
-) {
-    let size = window.size() + 256.0;
+fn apply_screen_wrap(arena: Res<Arena>, mut wrap_query: Query<&mut Transform, With<ScreenWrap>>) {
+    let size = arena.size + 256.0;
     let half_size = size / 2.0;
     for mut transform in &mut wrap_query {
         let position = transform.translation.xy();
@@ -1,17 +1,22 @@
 //! Player-specific behavior.
 
 use bevy::{
-    image::{ImageLoaderSettings, ImageSampler},
+    asset::{UntypedAssetId, VisitAssetDependencies},
     prelude::*,
 };
+use rand::prelude::*;
 
 use crate::{
     AppSystems, PausableSystems,
     app::{
-        animation::PlayerAnimation,
+        animation::{SpriteAnimationEvent, SpriteAnimator},
         movement::{MovementController, ScreenWrap},
     },
+    asset_manifest::{AssetManifest, FromAssetManifest, MissingAssetKey},
     asset_tracking::LoadResource,
+    audio::sound_effect,
+    rng::{GameRng, RngStream},
+    sprite_atlas::SpriteAtlas,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -27,29 +32,38 @@ pub(super) fn plugin(app: &mut App) {
             .in_set(AppSystems::RecordInput)
             .in_set(PausableSystems),
     );
+
+    // Animate and play sound effects based on controls.
+    app.add_systems(
+        Update,
+        (
+            update_player_animation,
+            trigger_step_sound_effect.run_if(resource_exists::<PlayerAssets>),
+        )
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
 }
 
 /// The player character.
 pub fn player(
     max_speed: f32,
     player_assets: &PlayerAssets,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    sprite_atlases: &Assets<SpriteAtlas>,
 ) -> impl Bundle {
     // A texture atlas is a way to split a single image into a grid of related images.
     // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let player_animation = PlayerAnimation::new();
+    let atlas = sprite_atlases
+        .get(&player_assets.ducky_atlas)
+        .expect("the player atlas is loaded with the player assets");
+    let animator = SpriteAnimator::new(player_assets.ducky_atlas.clone(), "idle");
 
     (
         Name::new("Player"),
         Player,
         Sprite {
             image: player_assets.ducky.clone(),
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout,
-                index: player_animation.get_atlas_index(),
-            }),
+            texture_atlas: Some(atlas.texture_atlas(animator.atlas_index(atlas))),
             ..default()
         },
         Transform::from_scale(Vec2::splat(8.0).extend(1.0)),
@@ -58,7 +72,7 @@ pub fn player(
             ..default()
         },
         ScreenWrap,
-        player_animation,
+        animator,
     )
 }
 
@@ -95,32 +109,68 @@ fn record_player_directional_input(
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
+/// Update the sprite direction and animation clip (idling/walking).
+fn update_player_animation(
+    mut player_query: Query<(&MovementController, &mut Sprite, &mut SpriteAnimator), With<Player>>,
+) {
+    for (controller, mut sprite, mut animator) in &mut player_query {
+        let dx = controller.intent.x;
+        if dx != 0.0 {
+            sprite.flip_x = dx < 0.0;
+        }
+
+        if controller.intent == Vec2::ZERO {
+            animator.play("idle");
+            animator.speed = 1.0;
+        } else {
+            animator.play("walking");
+            // Walk slower when the stick is only tilted a little.
+            animator.speed = controller.intent.length();
+        }
+    }
+}
+
+/// Play a step sound effect whenever the walking animation puts a foot down.
+fn trigger_step_sound_effect(
+    mut commands: Commands,
+    player_assets: Res<PlayerAssets>,
+    mut animation_events: EventReader<SpriteAnimationEvent>,
+    player_query: Query<(), With<Player>>,
+    mut rng: ResMut<GameRng>,
+) {
+    for event in animation_events.read() {
+        if event.name == "footstep" && player_query.contains(event.entity) {
+            // A theme can leave out the steps, which keeps the player quiet.
+            let random_step = player_assets.steps.choose(rng.stream(RngStream::Audio));
+            if let Some(random_step) = random_step.cloned() {
+                commands.spawn(sound_effect(random_step));
+            }
+        }
+    }
+}
+
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct PlayerAssets {
-    #[dependency]
     ducky: Handle<Image>,
-    #[dependency]
-    pub steps: Vec<Handle<AudioSource>>,
+    ducky_atlas: Handle<SpriteAtlas>,
+    steps: Vec<Handle<AudioSource>>,
 }
 
-impl FromWorld for PlayerAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            ducky: assets.load_with_settings(
-                "images/ducky.png",
-                |settings: &mut ImageLoaderSettings| {
-                    // Use `nearest` image sampling to preserve pixel art style.
-                    settings.sampler = ImageSampler::nearest();
-                },
-            ),
-            steps: vec![
-                assets.load("audio/sound_effects/step1.ogg"),
-                assets.load("audio/sound_effects/step2.ogg"),
-                assets.load("audio/sound_effects/step3.ogg"),
-                assets.load("audio/sound_effects/step4.ogg"),
-            ],
-        }
+impl FromAssetManifest for PlayerAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            ducky: manifest.image("ducky")?,
+            ducky_atlas: manifest.atlas("ducky")?,
+            steps: manifest.audio_variants("steps")?,
+        })
+    }
+}
+
+impl VisitAssetDependencies for PlayerAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.ducky.visit_dependencies(visit);
+        self.ducky_atlas.visit_dependencies(visit);
+        self.steps.visit_dependencies(visit);
     }
 }
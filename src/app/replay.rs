@@ -0,0 +1,290 @@
+//! Recording launchpad rounds and playing them back.
+//!
+//! A replay is the seed of the [`GameRng`], the rules and arena the round was played with, and
+//! the [`PlayerActions`] of every fixed tick. Everything else follows from those, so playing
+//! the actions back reproduces the round.
+//!
+//! Run the game with `--record <path>` to save each round when leaving the launchpad, and with
+//! `--replay <path>` to play a saved round the next time the launchpad is entered.
+
+use std::{error::Error, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::{
+        actions::{LauncherActions, PlayerActions, sample_actions},
+        arena::{Arena, fit_arena_to_window},
+        bits::ShellDesign,
+        coop::CoopMode,
+        launcher::{Launcher, spawn_launchers},
+        upgrades::{LauncherUpgrades, Progress},
+    },
+    cli,
+    game_rules::GameRules,
+    rng::{GameRng, seed_round},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    let settings = ReplaySettings::from_args();
+    if let Some(path) = &settings.replay {
+        match Replay::load(path) {
+            Ok(replay) => {
+                info!("Replaying {} on the launchpad", path.display());
+                app.insert_resource(ReplayPlayback::new(replay));
+            }
+            Err(error) => error!("Couldn't load the replay {}: {error}", path.display()),
+        }
+    }
+    app.insert_resource(settings);
+
+    app.add_systems(
+        OnEnter(Screen::Launchpad),
+        (
+            start_round.after(seed_round).before(spawn_launchers),
+            upgrade_launchers
+                .after(spawn_launchers)
+                .run_if(resource_exists::<ReplayPlayback>),
+        ),
+    );
+    app.add_systems(OnExit(Screen::Launchpad), save_recording);
+    app.add_systems(
+        FixedUpdate,
+        (
+            record_actions.run_if(resource_exists::<ReplayRecorder>),
+            play_actions.run_if(resource_exists::<ReplayPlayback>),
+        )
+            .after(sample_actions)
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
+    );
+    app.add_systems(
+        PreUpdate,
+        pin_arena
+            .after(fit_arena_to_window)
+            .run_if(resource_exists::<ReplayPlayback>.and(in_state(Screen::Launchpad))),
+    );
+}
+
+/// A recorded launchpad round.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub rules: GameRules,
+    /// The bits packed into the shells. Recordings from before bits had flavours use the
+    /// default design.
+    #[serde(default)]
+    pub design: ShellDesign,
+    /// The launcher upgrades bought by then. Recordings from before there were upgrades have
+    /// none.
+    #[serde(default)]
+    pub upgrades: LauncherUpgrades,
+    /// Whether a second player took part. Recordings from before co-op are solo.
+    #[serde(default)]
+    pub coop: CoopMode,
+    /// The width and height of the arena.
+    pub arena: (f32, f32),
+    /// The actions of every tick, with repeated actions stored once.
+    pub runs: Vec<ActionRun>,
+}
+
+/// The same actions taken for a number of consecutive ticks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ActionRun {
+    pub ticks: u32,
+    pub actions: LauncherActions,
+    /// What the second player did, if there was one.
+    #[serde(default)]
+    pub second_player: LauncherActions,
+}
+
+impl ActionRun {
+    fn player_actions(&self) -> PlayerActions {
+        PlayerActions([self.actions, self.second_player])
+    }
+}
+
+impl Replay {
+    fn new(
+        seed: u64,
+        rules: GameRules,
+        design: ShellDesign,
+        upgrades: LauncherUpgrades,
+        coop: CoopMode,
+        arena: Arena,
+    ) -> Self {
+        Self {
+            seed,
+            rules,
+            design,
+            upgrades,
+            coop,
+            arena: arena.size.into(),
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, actions: PlayerActions) {
+        match self.runs.last_mut() {
+            Some(run) if run.player_actions() == actions => run.ticks += 1,
+            _ => {
+                let PlayerActions([actions, second_player]) = actions;
+                self.runs.push(ActionRun {
+                    ticks: 1,
+                    actions,
+                    second_player,
+                });
+            }
+        }
+    }
+
+    pub fn load(path: &PathBuf) -> Result<Self, Box<dyn Error>> {
+        Ok(ron::de::from_bytes(&std::fs::read(path)?)?)
+    }
+
+    fn save(&self, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, ron)?;
+        Ok(())
+    }
+}
+
+/// Where to save recordings and which replay to play, from the command line.
+#[derive(Resource, Debug, Default)]
+pub struct ReplaySettings {
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+}
+
+impl ReplaySettings {
+    fn from_args() -> Self {
+        // There's no file system to read from or write to on the web.
+        if cfg!(target_family = "wasm") {
+            return Self::default();
+        }
+        Self {
+            record: cli::value("--record").map(PathBuf::from),
+            replay: cli::value("--replay").map(PathBuf::from),
+        }
+    }
+}
+
+/// The round being recorded.
+#[derive(Resource, Debug)]
+struct ReplayRecorder(Replay);
+
+/// The round being played back, and how far along it is.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    run: usize,
+    ticks: u32,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            run: 0,
+            ticks: 0,
+        }
+    }
+
+    /// The actions of the next tick, or `None` once the replay has ended.
+    fn next(&mut self) -> Option<PlayerActions> {
+        let run = self.replay.runs.get(self.run)?;
+        let actions = run.player_actions();
+        self.ticks += 1;
+        if self.ticks >= run.ticks {
+            self.run += 1;
+            self.ticks = 0;
+            if self.run == self.replay.runs.len() {
+                info!("The replay has ended");
+            }
+        }
+        Some(actions)
+    }
+}
+
+/// Reseed the round from the replay being played, or start recording it.
+fn start_round(
+    mut commands: Commands,
+    settings: Res<ReplaySettings>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    rules: Res<GameRules>,
+    design: Res<ShellDesign>,
+    progress: Res<Progress>,
+    coop: Res<CoopMode>,
+    arena: Res<Arena>,
+    mut rng: ResMut<GameRng>,
+) {
+    if let Some(mut playback) = playback {
+        playback.run = 0;
+        playback.ticks = 0;
+        *rng = GameRng::seeded(playback.replay.seed);
+        commands.insert_resource(playback.replay.rules.clone());
+        commands.insert_resource(playback.replay.design.clone());
+        commands.insert_resource(playback.replay.coop);
+        commands.insert_resource(Arena {
+            size: playback.replay.arena.into(),
+        });
+        return;
+    }
+
+    if settings.record.is_some() {
+        commands.insert_resource(ReplayRecorder(Replay::new(
+            rng.seed(),
+            rules.clone(),
+            design.clone(),
+            progress.upgrades,
+            *coop,
+            *arena,
+        )));
+    }
+}
+
+/// Give the launchers the upgrades they had in the recording rather than the player's own.
+fn upgrade_launchers(playback: Res<ReplayPlayback>, mut launchers: Query<&mut Launcher>) {
+    for mut launcher in &mut launchers {
+        launcher.upgrades = playback.replay.upgrades;
+    }
+}
+
+fn record_actions(actions: Res<PlayerActions>, mut recorder: ResMut<ReplayRecorder>) {
+    recorder.0.push(*actions);
+}
+
+/// Take the recorded actions instead of the player's, and leave the launcher idle once the
+/// replay has ended.
+fn play_actions(mut playback: ResMut<ReplayPlayback>, mut actions: ResMut<PlayerActions>) {
+    *actions = playback.next().unwrap_or_default();
+}
+
+/// Keep the arena the size it was recorded at, whatever the size of the window.
+fn pin_arena(playback: Res<ReplayPlayback>, mut arena: ResMut<Arena>) {
+    arena.set_if_neq(Arena {
+        size: playback.replay.arena.into(),
+    });
+}
+
+fn save_recording(
+    mut commands: Commands,
+    settings: Res<ReplaySettings>,
+    recorder: Option<Res<ReplayRecorder>>,
+) {
+    let (Some(recorder), Some(path)) = (recorder, &settings.record) else {
+        return;
+    };
+    match recorder.0.save(path) {
+        Ok(()) => info!("Saved the round to {}", path.display()),
+        Err(error) => error!("Couldn't save the round to {}: {error}", path.display()),
+    }
+    commands.remove_resource::<ReplayRecorder>();
+}
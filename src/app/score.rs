@@ -1,29 +1,70 @@
 use bevy::{prelude::*, ui::Val::*};
 
-use crate::{screens::Screen, theme::widget};
+use crate::{
+    PausableSystems,
+    app::coop::{CoopMode, PlayerId},
+    screens::Screen,
+    theme::widget,
+};
 
 #[derive(Event)]
 pub struct ScoreEvent {
     pub score_to_add: usize,
+    /// The player who scored, or `None` for points the players earned together.
+    pub player: Option<PlayerId>,
 }
 
 #[derive(Component)]
-struct Scoreboard {
+pub struct Scoreboard {
     score: usize,
+    player_scores: [usize; 2],
 }
 
 #[derive(Component)]
 struct LiveScore;
 
+/// The score of one player, shown when two share the launchpad.
+#[derive(Component)]
+struct LivePlayerScore(PlayerId);
+
 #[derive(Component)]
 struct ScoreboardGrid;
 
 impl Scoreboard {
-    fn update_score(&mut self, points: usize) {
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    pub fn player_score(&self, player: PlayerId) -> usize {
+        self.player_scores[player.index()]
+    }
+
+    /// Add points to the score and to the player who scored them. Points earned together are
+    /// split between the players, the first getting any left over.
+    fn update_score(&mut self, points: usize, player: Option<PlayerId>, coop: CoopMode) {
         self.score += points;
+        match player {
+            Some(player) => self.player_scores[player.index()] += points,
+            None => {
+                let players = coop.players();
+                for (index, &player) in players.iter().enumerate() {
+                    let share =
+                        points / players.len() + usize::from(index < points % players.len());
+                    self.player_scores[player.index()] += share;
+                }
+            }
+        }
     }
 }
 
+/// The width of each column of the scoreboard grid.
+const SCOREBOARD_COLUMN_WIDTH: f32 = 200.0;
+
+const SCOREBOARD_COLUMN_GAP: f32 = 30.0;
+
+/// How far the scoreboard reaches into the launchpad from the top right corner.
+pub const SCOREBOARD_WIDTH: f32 = 2.0 * SCOREBOARD_COLUMN_WIDTH + SCOREBOARD_COLUMN_GAP;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<ScoreEvent>();
 
@@ -34,59 +75,100 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnExit(Screen::Launchpad), despawn_scoreboard);
     app.add_systems(
         Update,
-        ((update_score).chain().run_if(in_state(Screen::Launchpad)),),
+        update_score
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
     );
 }
 
 pub fn score() -> impl Bundle {
-    (Scoreboard { score: 0 },)
+    (Scoreboard {
+        score: 0,
+        player_scores: [0; 2],
+    },)
 }
 
 fn init_score(mut commands: Commands) {
     commands.spawn(score());
 }
 
-fn spawn_scoreboard(mut commands: Commands, scoreboard: Single<&Scoreboard>) {
-    commands.spawn((
-        Name::new("Scoreboard Grid"),
-        GlobalZIndex(2),
-        Node {
-            justify_self: JustifySelf::End,
-            display: Display::Grid,
-            row_gap: Px(10.0),
-            column_gap: Px(30.0),
-            grid_template_columns: RepeatedGridTrack::px(2, 200.0),
-            ..default()
-        },
-        children![
-            (
-                widget::label("Score:"),
+fn spawn_scoreboard(mut commands: Commands, coop: Res<CoopMode>, scoreboard: Single<&Scoreboard>) {
+    let grid = commands
+        .spawn((
+            Name::new("Scoreboard Grid"),
+            GlobalZIndex(2),
+            Node {
+                justify_self: JustifySelf::End,
+                display: Display::Grid,
+                row_gap: Px(10.0),
+                column_gap: Px(SCOREBOARD_COLUMN_GAP),
+                grid_template_columns: RepeatedGridTrack::px(2, SCOREBOARD_COLUMN_WIDTH),
+                ..default()
+            },
+            children![
+                (
+                    widget::label("Score:"),
+                    Node {
+                        justify_self: JustifySelf::Start,
+                        ..default()
+                    },
+                ),
+                (
+                    widget::label(scoreboard.score.to_string()),
+                    Node {
+                        justify_self: JustifySelf::Start,
+                        ..default()
+                    },
+                    LiveScore,
+                )
+            ],
+            ScoreboardGrid,
+        ))
+        .id();
+
+    // Each player gets a row of their own when two share the launchpad.
+    if *coop == CoopMode::Solo {
+        return;
+    }
+    for &player in coop.players() {
+        commands
+            .spawn((
+                widget::label(format!("{}:", player.name())),
                 Node {
                     justify_self: JustifySelf::Start,
                     ..default()
                 },
-            ),
-            (
-                widget::label(scoreboard.score.to_string()),
+                ChildOf(grid),
+            ))
+            // The label comes with a colour of its own, so the player's replaces it after.
+            .insert(TextColor(player.color()));
+        commands
+            .spawn((
+                widget::label(scoreboard.player_score(player).to_string()),
                 Node {
                     justify_self: JustifySelf::Start,
                     ..default()
                 },
-                LiveScore,
-            )
-        ],
-        ScoreboardGrid,
-    ));
+                LivePlayerScore(player),
+                ChildOf(grid),
+            ))
+            .insert(TextColor(player.color()));
+    }
 }
 
 fn update_score(
+    coop: Res<CoopMode>,
     mut score_events: EventReader<ScoreEvent>,
     mut scoreboard: Single<&mut Scoreboard>,
     mut score: Single<&mut Text, With<LiveScore>>,
+    mut player_scores: Query<(&mut Text, &LivePlayerScore), Without<LiveScore>>,
 ) {
     for event in score_events.read() {
-        scoreboard.update_score(event.score_to_add);
+        scoreboard.update_score(event.score_to_add, event.player, *coop);
         score.0 = scoreboard.score.to_string();
+        for (mut text, player_score) in &mut player_scores {
+            text.0 = scoreboard.player_score(player_score.0).to_string();
+        }
     }
 }
 
@@ -0,0 +1,439 @@
+//! A headless launchpad for balancing, built with the `simulation` feature:
+//! `cargo run --features simulation -- <options>`.
+//!
+//! Rounds of free play run on the game's own plugins without a window, audio or rendering, with
+//! an AI shooter operating the launcher in place of the player. Every update advances the clock
+//! by the same fixed step, so a round plays out the same however fast the machine is. Rounds are
+//! played for every difficulty preset (or the one given with `--difficulty`) and written to a
+//! CSV or JSON report of kills, accuracy and time-to-clear. Nothing the rounds do is saved.
+//!
+//! Options:
+//! - `--rounds <n>`: rounds per difficulty, 10 by default.
+//! - `--uaps <n>`: UAPs spawned per round, 20 by default.
+//! - `--time-limit <secs>`: when a round that hasn't been cleared ends, 180 by default.
+//! - `--arena <width>x<height>`: the size of the playing field, 1920x1080 by default.
+//! - `--shooter aim|random`: whether the shooter leads its targets or fires at random angles.
+//! - `--difficulty <preset>`: only simulate one difficulty.
+//! - `--seed <n>`: seed the first round with `n` and each following one with the next number,
+//!   so that the same rounds are played again. Random by default.
+//! - `--report <path>`: where to write the report. Paths ending in `.json` are written as JSON,
+//!   everything else as CSV. `simulation_report.csv` by default.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{
+    AppSystems,
+    app::{
+        actions::{LauncherActions, PlayerActions, sample_actions},
+        arena::Arena,
+        launcher::{ActiveLauncher, Launcher},
+        uap::{DestroyUapEvent, Uap},
+        uap_damage::UapHitEvent,
+    },
+    cli,
+    game_rules::{Difficulty, GameRules},
+    headless,
+    rng::{GameRng, RngStream, RoundSeed},
+    screens::Screen,
+};
+
+/// How long to wait for the assets before giving up.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How close to its target angle the launcher has to be before the shooter fires, in radians.
+const AIM_TOLERANCE: f32 = 0.02;
+
+/// Run the simulation with the options passed on the command line.
+pub fn run() -> AppExit {
+    let settings = match SimulationSettings::from_args() {
+        Ok(settings) => settings,
+        Err(error) => {
+            eprintln!("{error}");
+            return AppExit::error();
+        }
+    };
+
+    let mut app = headless::app();
+    if !headless::load_assets(&mut app, LOAD_TIMEOUT) {
+        eprintln!("Timed out loading the game's assets");
+        return AppExit::error();
+    }
+    app.insert_resource(settings.arena);
+    app.add_observer(count_spawned_uaps);
+    app.add_systems(
+        FixedUpdate,
+        (
+            operate_launcher
+                .in_set(AppSystems::RecordInput)
+                .after(sample_actions),
+            record_round.after(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Launchpad).and(resource_exists::<SimulatedRound>)),
+    );
+
+    let mut reports = Vec::new();
+    for difficulty in &settings.difficulties {
+        for round in 1..=settings.rounds {
+            let seed = settings
+                .seed
+                .map_or_else(rand::random, |seed| seed.wrapping_add(round as u64 - 1));
+            let stats = simulate_round(&mut app, &settings, *difficulty, seed);
+            reports.push(RoundReport::new(*difficulty, round, &stats));
+        }
+        print_summary(*difficulty, &reports);
+    }
+
+    if let Err(error) = write_report(&settings.report, &reports) {
+        eprintln!("Failed to write {}: {error}", settings.report.display());
+        return AppExit::error();
+    }
+    println!("Wrote {}", settings.report.display());
+    AppExit::Success
+}
+
+struct SimulationSettings {
+    rounds: usize,
+    uaps_per_round: usize,
+    time_limit_secs: f32,
+    arena: Arena,
+    shooter: ShooterMode,
+    difficulties: Vec<Difficulty>,
+    seed: Option<u64>,
+    report: PathBuf,
+}
+
+impl SimulationSettings {
+    fn from_args() -> Result<Self, String> {
+        let mut settings = Self {
+            rounds: 10,
+            uaps_per_round: 20,
+            time_limit_secs: 180.0,
+            arena: Arena::default(),
+            shooter: ShooterMode::Aim,
+            difficulties: Difficulty::ALL.to_vec(),
+            seed: None,
+            report: PathBuf::from("simulation_report.csv"),
+        };
+
+        let invalid = |name: &str, value: &str| format!("Invalid value for {name}: {value}");
+
+        if let Some(rounds) = cli::value("--rounds") {
+            settings.rounds = rounds.parse().map_err(|_| invalid("--rounds", &rounds))?;
+        }
+        if let Some(uaps) = cli::value("--uaps") {
+            settings.uaps_per_round = uaps.parse().map_err(|_| invalid("--uaps", &uaps))?;
+        }
+        if let Some(secs) = cli::value("--time-limit") {
+            settings.time_limit_secs = secs.parse().map_err(|_| invalid("--time-limit", &secs))?;
+        }
+        if let Some(size) = cli::value("--arena") {
+            let (width, height) = size
+                .split_once('x')
+                .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+                .ok_or_else(|| invalid("--arena", &size))?;
+            settings.arena.size = Vec2::new(width, height);
+        }
+        if let Some(shooter) = cli::value("--shooter") {
+            settings.shooter = match shooter.as_str() {
+                "aim" => ShooterMode::Aim,
+                "random" => ShooterMode::Random,
+                _ => return Err(invalid("--shooter", &shooter)),
+            };
+        }
+        if let Some(name) = cli::value("--difficulty") {
+            let difficulty = Difficulty::ALL
+                .into_iter()
+                .find(|difficulty| difficulty.name().eq_ignore_ascii_case(&name))
+                .ok_or_else(|| invalid("--difficulty", &name))?;
+            settings.difficulties = vec![difficulty];
+        }
+        if let Some(seed) = cli::value("--seed") {
+            settings.seed = Some(seed.parse().map_err(|_| invalid("--seed", &seed))?);
+        }
+        if let Some(path) = cli::value("--report") {
+            settings.report = PathBuf::from(path);
+        }
+        Ok(settings)
+    }
+}
+
+/// Play a single round on the launchpad to the end and return what happened.
+fn simulate_round(
+    app: &mut App,
+    settings: &SimulationSettings,
+    difficulty: Difficulty,
+    seed: u64,
+) -> RoundStats {
+    // Setting the difficulty, even to the same one, puts its preset back in place.
+    *app.world_mut().resource_mut::<Difficulty>() = difficulty;
+    app.insert_resource(RoundSeed::Fixed(seed));
+    app.update();
+
+    app.insert_resource(SimulatedRound {
+        shooter: settings.shooter,
+        uaps: settings.uaps_per_round,
+        spawned: 0,
+        target: None,
+        secs: 0.0,
+        stats: RoundStats::default(),
+    });
+    set_screen(app, Screen::Launchpad);
+    loop {
+        app.update();
+        let round = app.world().resource::<SimulatedRound>();
+        if round.stats.time_to_clear.is_some() || round.secs >= settings.time_limit_secs {
+            break;
+        }
+    }
+    set_screen(app, Screen::Workshop);
+    app.world_mut()
+        .remove_resource::<SimulatedRound>()
+        .expect("the round is inserted when it starts")
+        .stats
+}
+
+fn set_screen(app: &mut App, screen: Screen) {
+    app.world_mut()
+        .resource_mut::<NextState<Screen>>()
+        .set(screen);
+    app.update();
+}
+
+/// The round being played and how it's going.
+#[derive(Resource)]
+struct SimulatedRound {
+    shooter: ShooterMode,
+    /// How many UAPs the round spawns in all.
+    uaps: usize,
+    spawned: usize,
+    /// The angle a random shooter is turning towards.
+    target: Option<f32>,
+    /// How long the round has been going.
+    secs: f32,
+    stats: RoundStats,
+}
+
+#[derive(Default, Debug)]
+struct RoundStats {
+    kills: usize,
+    shots: usize,
+    hits: usize,
+    /// Seconds until every UAP of the round was destroyed, if they were.
+    time_to_clear: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ShooterMode {
+    /// Lead the UAP that's quickest to aim at.
+    Aim,
+    /// Fire at random angles.
+    Random,
+}
+
+/// Stop spawning once the round's UAPs are all out, by letting no more on at once.
+fn count_spawned_uaps(
+    _: Trigger<OnAdd, Uap>,
+    round: Option<ResMut<SimulatedRound>>,
+    mut rules: ResMut<GameRules>,
+) {
+    let Some(mut round) = round else {
+        return;
+    };
+    round.spawned += 1;
+    if round.spawned >= round.uaps {
+        rules.max_uaps = 0;
+    }
+}
+
+/// Turn the launcher being operated towards the shooter's target, and fire once it's lined up
+/// and reloaded.
+fn operate_launcher(
+    time: Res<Time>,
+    rules: Res<GameRules>,
+    mut round: ResMut<SimulatedRound>,
+    mut actions: ResMut<PlayerActions>,
+    launcher_query: Single<(&Transform, &Launcher), With<ActiveLauncher>>,
+    uap_query: Query<(&Transform, &Uap), Without<Launcher>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let (transform, launcher) = *launcher_query;
+    let max_angle = rules.launcher_max_angle + launcher.upgrades.extra_aim_angle();
+    let current = transform.rotation.to_euler(EulerRot::ZYX).0;
+    let target = match round.shooter {
+        ShooterMode::Aim => {
+            let pivot = transform.translation.xy();
+            // The spawners UAPs belong to stay at the origin, so their own transforms place them.
+            uap_query
+                .iter()
+                .map(|(uap_transform, uap)| {
+                    lead_angle(
+                        pivot,
+                        uap_transform.translation.xy(),
+                        uap.velocity(),
+                        rules.projectile_speed,
+                    )
+                })
+                .filter(|angle| angle.abs() <= max_angle)
+                .min_by(|a, b| (a - current).abs().total_cmp(&(b - current).abs()))
+        }
+        // The shooter draws from the spawning stream as well, so the seed decides its aim.
+        ShooterMode::Random => Some(*round.target.get_or_insert_with(|| {
+            rng.stream(RngStream::Spawning)
+                .gen_range(-max_angle..=max_angle)
+        })),
+    };
+
+    let mut player = LauncherActions::default();
+    if let Some(target) = target {
+        // Turning stops within half a step of the target, so that it doesn't overshoot back
+        // and forth.
+        let step = rules.launcher_rotation_speed
+            * launcher.upgrades.rotation_speed_scale()
+            * time.delta_secs();
+        let offset = target - current;
+        if offset.abs() > step / 2.0 {
+            player.turn = offset.signum() as i8;
+        } else if offset.abs() < AIM_TOLERANCE.max(step / 2.0) && launcher.reloaded() {
+            player.fire = true;
+            round.stats.shots += 1;
+            round.target = None;
+        }
+    }
+    actions.0[0] = player;
+}
+
+/// The launcher angle that puts a shell where a UAP moving at `velocity` will be.
+fn lead_angle(pivot: Vec2, target: Vec2, velocity: Vec2, projectile_speed: f32) -> f32 {
+    let mut aim = target;
+    for _ in 0..3 {
+        let flight_secs = pivot.distance(aim) / projectile_speed;
+        aim = target + velocity * flight_secs;
+    }
+    let offset = aim - pivot;
+    // The launcher points up when it isn't rotated.
+    (-offset.x).atan2(offset.y)
+}
+
+/// Count hits and kills, and note when the last of the round's UAPs is gone.
+fn record_round(
+    time: Res<Time>,
+    mut round: ResMut<SimulatedRound>,
+    mut hit_events: EventReader<UapHitEvent>,
+    mut destroy_events: EventReader<DestroyUapEvent>,
+    uap_query: Query<(), With<Uap>>,
+) {
+    round.secs += time.delta_secs();
+    round.stats.hits += hit_events.read().count();
+
+    let mut destroyed = Vec::new();
+    for event in destroy_events.read() {
+        // Two shells can destroy the same UAP in one update.
+        if !destroyed.contains(&event.entity) {
+            destroyed.push(event.entity);
+        }
+    }
+    round.stats.kills += destroyed.len();
+
+    if round.stats.time_to_clear.is_none() && round.spawned >= round.uaps && uap_query.is_empty() {
+        round.stats.time_to_clear = Some(round.secs);
+    }
+}
+
+/// A row of the report.
+#[derive(Serialize, Debug)]
+struct RoundReport {
+    difficulty: Difficulty,
+    round: usize,
+    kills: usize,
+    shots: usize,
+    hits: usize,
+    /// The fraction of shots that hit a UAP.
+    accuracy: f32,
+    time_to_clear: Option<f32>,
+}
+
+impl RoundReport {
+    fn new(difficulty: Difficulty, round: usize, stats: &RoundStats) -> Self {
+        Self {
+            difficulty,
+            round,
+            kills: stats.kills,
+            shots: stats.shots,
+            hits: stats.hits,
+            accuracy: if stats.shots == 0 {
+                0.0
+            } else {
+                stats.hits as f32 / stats.shots as f32
+            },
+            time_to_clear: stats.time_to_clear,
+        }
+    }
+}
+
+fn print_summary(difficulty: Difficulty, reports: &[RoundReport]) {
+    let rounds = reports
+        .iter()
+        .filter(|report| report.difficulty == difficulty)
+        .collect::<Vec<_>>();
+    if rounds.is_empty() {
+        return;
+    }
+    let count = rounds.len() as f32;
+    let kills = rounds.iter().map(|report| report.kills as f32).sum::<f32>() / count;
+    let accuracy = rounds.iter().map(|report| report.accuracy).sum::<f32>() / count;
+    let clear_times = rounds
+        .iter()
+        .filter_map(|report| report.time_to_clear)
+        .collect::<Vec<_>>();
+    let clear_time = if clear_times.is_empty() {
+        "-".to_string()
+    } else {
+        format!(
+            "{:.1}s",
+            clear_times.iter().sum::<f32>() / clear_times.len() as f32
+        )
+    };
+    println!(
+        "{:<7} kills {kills:5.1}  accuracy {:5.1}%  cleared {}/{}  time to clear {clear_time}",
+        difficulty.name(),
+        100.0 * accuracy,
+        clear_times.len(),
+        rounds.len(),
+    );
+}
+
+fn write_report(path: &Path, reports: &[RoundReport]) -> Result<(), Box<dyn std::error::Error>> {
+    let contents = if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::to_string_pretty(reports)?
+    } else {
+        let mut csv = "difficulty,round,kills,shots,hits,accuracy,time_to_clear\n".to_string();
+        for report in reports {
+            csv += &format!(
+                "{},{},{},{},{},{:.3},{}\n",
+                report.difficulty.name(),
+                report.round,
+                report.kills,
+                report.shots,
+                report.hits,
+                report.accuracy,
+                report
+                    .time_to_clear
+                    .map_or(String::new(), |secs| format!("{secs:.2}")),
+            );
+        }
+        csv
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Gameplay sound effects for launching shells, fireworks bursting and UAPs being destroyed.
+
+use bevy::{
+    asset::{UntypedAssetId, VisitAssetDependencies},
+    audio::Volume,
+    prelude::*,
+};
+use rand::prelude::*;
+
+use crate::{
+    app::uap::Uap,
+    asset_manifest::{AssetManifest, FromAssetManifest, MissingAssetKey},
+    asset_tracking::LoadResource,
+    audio::{SoundEffect, positional, sound_effect_varied},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SoundEffectAssets>();
+    app.load_resource::<SoundEffectAssets>();
+
+    app.add_observer(start_uap_hum);
+}
+
+/// Bursts at or above this size layer a deep boom underneath the regular bang.
+const LARGE_BURST_SIZE: f32 = 1.0;
+
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct SoundEffectAssets {
+    launches: Vec<Handle<AudioSource>>,
+    fuse: Handle<AudioSource>,
+    explosions_small: Vec<Handle<AudioSource>>,
+    explosions_large: Vec<Handle<AudioSource>>,
+    crackles: Vec<Handle<AudioSource>>,
+    uap_destroyed: Vec<Handle<AudioSource>>,
+    uap_hum: Handle<AudioSource>,
+}
+
+impl FromAssetManifest for SoundEffectAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            launches: manifest.audio_variants("launch")?,
+            fuse: manifest.audio("fuse")?,
+            explosions_small: manifest.audio_variants("explosion_small")?,
+            explosions_large: manifest.audio_variants("explosion_large")?,
+            crackles: manifest.audio_variants("crackle")?,
+            uap_destroyed: manifest.audio_variants("uap_destroyed")?,
+            uap_hum: manifest.audio("uap_hum")?,
+        })
+    }
+}
+
+impl VisitAssetDependencies for SoundEffectAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.launches.visit_dependencies(visit);
+        self.fuse.visit_dependencies(visit);
+        self.explosions_small.visit_dependencies(visit);
+        self.explosions_large.visit_dependencies(visit);
+        self.crackles.visit_dependencies(visit);
+        self.uap_destroyed.visit_dependencies(visit);
+        self.uap_hum.visit_dependencies(visit);
+    }
+}
+
+impl SoundEffectAssets {
+    /// Play the thump of a shell leaving the launcher, followed by its fuse fizzing.
+    pub fn play_launch(&self, commands: &mut Commands, position: Vec3, rng: &mut impl Rng) {
+        if let Some(launch) = self.launches.choose(rng).cloned() {
+            commands.spawn((
+                sound_effect_varied(launch, 1.0, 1.0, 0.1, rng),
+                positional(position),
+            ));
+        }
+        commands.spawn((
+            sound_effect_varied(self.fuse.clone(), 1.0, 0.4, 0.2, rng),
+            positional(position),
+        ));
+    }
+
+    /// Play a firework burst. Bigger bursts sound deeper and louder, add a low boom
+    /// layer and leave a longer crackling tail. Layers the manifest has no variants of are left
+    /// out.
+    pub fn play_explosion(
+        &self,
+        commands: &mut Commands,
+        position: Vec3,
+        burst_size: f32,
+        rng: &mut impl Rng,
+    ) {
+        let burst_size = burst_size.max(0.1);
+        // Larger bursts are pitched down so they feel heavier.
+        let pitch_scale = 1.0 / burst_size.sqrt();
+
+        if let Some(bang) = self.explosions_small.choose(rng).cloned() {
+            commands.spawn((
+                sound_effect_varied(bang, pitch_scale, 0.8 * burst_size.min(1.5), 0.15, rng),
+                positional(position),
+            ));
+        }
+
+        let boom = (burst_size >= LARGE_BURST_SIZE)
+            .then(|| self.explosions_large.choose(rng).cloned())
+            .flatten();
+        if let Some(boom) = boom {
+            commands.spawn((
+                sound_effect_varied(boom, pitch_scale, 0.7, 0.1, rng),
+                positional(position),
+            ));
+        }
+
+        if let Some(crackle) = self.crackles.choose(rng).cloned() {
+            commands.spawn((
+                sound_effect_varied(crackle, 1.0, 0.3 * burst_size.min(2.0), 0.2, rng),
+                positional(position),
+            ));
+        }
+    }
+
+    /// Play the sound of a UAP breaking apart.
+    pub fn play_uap_destroyed(&self, commands: &mut Commands, position: Vec3, rng: &mut impl Rng) {
+        if let Some(destroyed) = self.uap_destroyed.choose(rng).cloned() {
+            commands.spawn((
+                sound_effect_varied(destroyed, 1.0, 0.8, 0.12, rng),
+                positional(position),
+            ));
+        }
+    }
+}
+
+/// Attach a looping engine hum to every new UAP, which follows it around the screen.
+fn start_uap_hum(
+    trigger: Trigger<OnAdd, Uap>,
+    mut commands: Commands,
+    sound_effect_assets: Option<Res<SoundEffectAssets>>,
+) {
+    let Some(sound_effect_assets) = sound_effect_assets else {
+        return;
+    };
+
+    commands.entity(trigger.target()).with_child((
+        Name::new("UAP Hum"),
+        AudioPlayer(sound_effect_assets.uap_hum.clone()),
+        PlaybackSettings::LOOP.with_volume(Volume::Linear(0.15)),
+        SoundEffect,
+        positional(Vec3::ZERO),
+    ));
+}
@@ -0,0 +1,158 @@
+//! Time attack rounds: score as much as possible before the clock runs out. Destroying several
+//! UAPs in quick succession earns bonus time.
+//!
+//! The clock only runs in fixed steps while the game isn't paused.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::{
+        game_mode::{EndRound, GameMode},
+        score::SCOREBOARD_WIDTH,
+        uap::DestroyUapEvent,
+    },
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<RoundClock>();
+
+    app.add_systems(
+        OnEnter(Screen::Launchpad),
+        start_time_attack.run_if(resource_equals(GameMode::TimeAttack)),
+    );
+    app.add_systems(OnExit(Screen::Launchpad), end_time_attack);
+    app.add_systems(
+        FixedUpdate,
+        (tick_round_clock, award_bonus_time)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad).and(resource_exists::<RoundClock>)),
+    );
+    // Scores are counted in `Update`, so the points for the last tick are in by then.
+    app.add_systems(
+        PostUpdate,
+        time_up.run_if(in_state(Screen::Launchpad).and(resource_exists::<RoundClock>)),
+    );
+    app.add_systems(
+        Update,
+        update_clock_label.run_if(resource_exists_and_changed::<RoundClock>),
+    );
+}
+
+/// How long a time attack round lasts without bonus time, in seconds.
+const ROUND_SECS: f32 = 90.0;
+
+/// The space between the clock and the scoreboard to its right, in pixels.
+const CLOCK_GAP: f32 = 20.0;
+
+/// How soon after the previous kill the next one has to come to count towards a multi-kill,
+/// in seconds.
+const MULTI_KILL_WINDOW_SECS: f32 = 1.0;
+
+/// Seconds added for every kill of a multi-kill after the first.
+const BONUS_SECS_PER_KILL: f32 = 2.0;
+
+/// The time left in the time attack round being played.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct RoundClock {
+    pub remaining_secs: f32,
+    /// Kills in the current multi-kill.
+    chain: u32,
+    /// Seconds since the last kill.
+    since_kill_secs: f32,
+    /// Bonus seconds earned by the current multi-kill.
+    bonus_secs: f32,
+}
+
+impl RoundClock {
+    fn new(secs: f32) -> Self {
+        Self {
+            remaining_secs: secs,
+            chain: 0,
+            since_kill_secs: f32::INFINITY,
+            bonus_secs: 0.0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct ClockLabel;
+
+fn start_time_attack(mut commands: Commands) {
+    let clock = RoundClock::new(ROUND_SECS);
+    commands.insert_resource(clock);
+    // Next to the scoreboard grid in the top right corner.
+    commands.spawn((
+        Name::new("Round Clock"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(0.0),
+            right: Px(SCOREBOARD_WIDTH + CLOCK_GAP),
+            ..default()
+        },
+        GlobalZIndex(2),
+        StateScoped(Screen::Launchpad),
+        children![(widget::label(clock_text(&clock)), ClockLabel)],
+    ));
+}
+
+fn end_time_attack(mut commands: Commands) {
+    commands.remove_resource::<RoundClock>();
+}
+
+fn clock_text(clock: &RoundClock) -> String {
+    let secs = clock.remaining_secs.max(0.0).ceil() as u32;
+    let time = format!("Time: {}:{:02}", secs / 60, secs % 60);
+    if clock.bonus_secs > 0.0 {
+        format!("{time} (+{}s)", clock.bonus_secs)
+    } else {
+        time
+    }
+}
+
+fn tick_round_clock(time: Res<Time>, mut clock: ResMut<RoundClock>) {
+    if clock.remaining_secs <= 0.0 {
+        return;
+    }
+    clock.remaining_secs -= time.delta_secs();
+    clock.since_kill_secs += time.delta_secs();
+    if clock.since_kill_secs > MULTI_KILL_WINDOW_SECS {
+        clock.chain = 0;
+        clock.bonus_secs = 0.0;
+    }
+}
+
+/// End the round once the clock has run out.
+fn time_up(mut commands: Commands, clock: Res<RoundClock>) {
+    if clock.remaining_secs <= 0.0 {
+        commands.queue(EndRound { reason: "Time up" });
+    }
+}
+
+fn award_bonus_time(
+    mut destroy_events: EventReader<DestroyUapEvent>,
+    mut clock: ResMut<RoundClock>,
+) {
+    for _ in destroy_events.read() {
+        if clock.remaining_secs <= 0.0 {
+            return;
+        }
+        clock.chain += 1;
+        clock.since_kill_secs = 0.0;
+        if clock.chain > 1 {
+            clock.remaining_secs += BONUS_SECS_PER_KILL;
+            clock.bonus_secs += BONUS_SECS_PER_KILL;
+        }
+    }
+}
+
+fn update_clock_label(clock: Res<RoundClock>, mut label_query: Query<&mut Text, With<ClockLabel>>) {
+    for mut text in &mut label_query {
+        text.0 = clock_text(&clock);
+    }
+}
@@ -1,144 +1,328 @@
-use bevy::prelude::*;
-use bevy_enoki::prelude::*;
+use bevy::{
+    asset::{UntypedAssetId, VisitAssetDependencies},
+    prelude::*,
+};
+use rand::Rng;
 
 use crate::{
-    PausableSystems,
+    AppSystems, PausableSystems,
     app::{
+        animation::SpriteAnimator,
+        arena::Arena,
+        bits::{DamageMix, Defences},
+        coop::PlayerId,
         movement::{MovementController, ScreenWrap},
         score::ScoreEvent,
-        uap_animation::UapAnimation,
+        sound_effects::SoundEffectAssets,
+        uap_debris::spawn_debris,
     },
+    asset_manifest::{AssetManifest, FromAssetManifest, MissingAssetKey},
     asset_tracking::LoadResource,
+    game_rules::GameRules,
+    rng::{GameRng, RngStream},
+    sprite_atlas::SpriteAtlas,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<DestroyUapEvent>();
 
     app.register_type::<Uap>();
+    app.register_type::<Hitbox>();
+    app.register_type::<Shielded>();
 
     app.register_type::<UapAssets>();
     app.load_resource::<UapAssets>();
 
-    // Record directional input as movement controls.
+    app.add_systems(Update, update_uap_animation.in_set(PausableSystems));
+    // UAPs move and get destroyed in fixed steps, so that rounds can be replayed.
     app.add_systems(
-        Update,
-        (uap_movement, handle_destroy_events).in_set(PausableSystems),
+        FixedUpdate,
+        (uap_movement, handle_destroy_events)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
     );
 }
 
 #[derive(Event)]
 pub struct DestroyUapEvent {
-    entity: Entity,
+    pub(super) entity: Entity,
     transform: Transform,
+    /// What brought the UAP down.
+    source: DamageSource,
+}
+
+/// Where damage to a UAP came from: the player who dealt it and the flavours it carried.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Default)]
+pub struct DamageSource {
+    pub player: PlayerId,
+    pub mix: DamageMix,
 }
 
 pub fn uap(
     max_speed: f32,
+    kind: Uap,
     uap_assets: &UapAssets,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    sprite_atlases: &Assets<SpriteAtlas>,
+    rng: &mut impl Rng,
 ) -> impl Bundle {
-    // A texture atlas is a way to split a single image into a grid of related images.
-    // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-    let layout = TextureAtlasLayout::from_grid(UVec2::new(64, 32), 4, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let uap_animation = UapAnimation::new();
-    let x_spawn = rand::random::<f32>() * 400.0 - 400.0;
-    let y_spawn = rand::random::<f32>() * 600.0 - 200.0;
+    let (mut sprite, animator) = uap_assets.animated_sprite(sprite_atlases);
+    // Shielded UAPs take on the colour of the only flavour that gets through.
+    if let Some(shield) = kind.defences.shield {
+        sprite.color = shield.color();
+    }
     (
         Name::new("UAP"),
-        Uap { ..default() },
-        Sprite {
-            image: uap_assets.uap.clone(),
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout,
-                index: uap_animation.get_atlas_index(),
-            }),
-            ..default()
-        },
+        kind,
+        sprite,
+        animator,
         Transform::from_scale(Vec2::splat(1.0).extend(1.0))
-            .with_translation(Vec3::new(x_spawn, y_spawn, 0.0)),
+            .with_translation(spawn_position(rng).extend(0.0)),
         MovementController {
             max_speed,
             ..default()
         },
         ScreenWrap,
-        uap_animation,
     )
 }
 
+/// A random position for a new UAP.
+pub(super) fn spawn_position(rng: &mut impl Rng) -> Vec2 {
+    Vec2::new(rng.gen_range(-400.0..0.0), rng.gen_range(-200.0..400.0))
+}
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
 #[reflect(Component)]
 pub struct Uap {
     speed: f32,
     direction: f32,
+    /// How fast the UAP climbs (or sinks, when negative), in pixels per second.
+    drift: f32,
     margin: f32,
     health: f32,
+    max_health: f32,
+    tough: bool,
+    /// Whether the UAP stays put when hit, like the parts of a mothership.
+    anchored: bool,
+    defences: Defences,
 }
 
+/// The size of a frame of the UAP sprite.
+pub const UAP_FRAME_SIZE: Vec2 = Vec2::new(56.0, 24.0);
+
+/// The size of a UAP's collider, for UAPs that don't fill their sprite's frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Hitbox(pub Vec2);
+
+impl Hitbox {
+    /// The size a UAP is hit within: its hitbox if it has one, or else its sprite.
+    pub fn size_of(hitbox: Option<&Hitbox>, sprite: &Sprite) -> Vec2 {
+        hitbox.map_or(sprite.custom_size.unwrap_or(UAP_FRAME_SIZE), |hitbox| {
+            hitbox.0
+        })
+    }
+}
+
+/// Shells burst harmlessly against a shielded UAP.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Shielded;
+
 impl Uap {
+    fn new(speed: f32, health: f32, tough: bool, rng: &mut impl Rng) -> Self {
+        Self {
+            speed,
+            direction: if rng.r#gen() { 1.0 } else { -1.0 },
+            drift: 0.0,
+            margin: 50.0,
+            health,
+            max_health: health,
+            tough,
+            anchored: false,
+            defences: Defences::default(),
+        }
+    }
+
+    pub fn regular(rules: &GameRules, rng: &mut impl Rng) -> Self {
+        Self::new(rules.uap_speed, rules.uap_health, false, rng)
+    }
+
+    /// A slower, armoured UAP that takes more hits.
+    pub fn tough(rules: &GameRules, rng: &mut impl Rng) -> Self {
+        Self::new(rules.tough_uap_speed, rules.tough_uap_health, true, rng)
+    }
+
+    /// An armoured part of a mothership, which moves with the mothership instead of flying by
+    /// itself.
+    pub fn boss_part(health: f32) -> Self {
+        Self {
+            speed: 0.0,
+            direction: 1.0,
+            drift: 0.0,
+            margin: 0.0,
+            health,
+            max_health: health,
+            tough: true,
+            anchored: true,
+            defences: Defences::default(),
+        }
+    }
+
+    /// Make the UAP climb or sink as it flies, so that it can escape off the top or bottom.
+    pub fn with_drift(mut self, drift: f32) -> Self {
+        self.drift = drift;
+        self
+    }
+
+    pub fn with_defences(mut self, defences: Defences) -> Self {
+        self.defences = defences;
+        self
+    }
+
+    /// The current velocity, in pixels per second.
+    pub fn velocity(&self) -> Vec2 {
+        Vec2::new(self.speed * self.direction, self.drift)
+    }
+
+    pub fn health(&self) -> f32 {
+        self.health.max(0.0)
+    }
+
+    /// The remaining health, between 0 and 1.
+    pub fn health_fraction(&self) -> f32 {
+        (self.health / self.max_health).clamp(0.0, 1.0)
+    }
+
+    /// Whether this UAP is sturdier than a regular one.
+    pub fn is_tough(&self) -> bool {
+        self.tough
+    }
+
+    /// How far hits knock this UAP back, relative to a regular one.
+    pub fn knockback_scale(&self) -> f32 {
+        if self.anchored {
+            0.0
+        } else if self.tough {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether this UAP is below half health and showing it.
+    pub fn is_damaged(&self) -> bool {
+        self.health_fraction() < 0.5
+    }
+
+    /// The damage a hit with the given mix of flavours does once the UAP's defences have had
+    /// their say, or `None` if nothing gets through.
+    pub fn damage_through_defences(
+        &self,
+        damage: f32,
+        mix: &DamageMix,
+        shielded: bool,
+    ) -> Option<f32> {
+        let damage = damage * self.defences.multiplier(mix);
+        (!shielded && damage > 0.0).then_some(damage)
+    }
+
+    /// Returns true if the damage destroyed the UAP, which scores for the player who did it.
     pub fn take_damage(
         &mut self,
         damage: f32,
+        source: DamageSource,
         entity: Entity,
         transform: &Transform,
         destroy_events: &mut EventWriter<DestroyUapEvent>,
         score_events: &mut EventWriter<ScoreEvent>,
-    ) {
+    ) -> bool {
         self.health -= damage;
 
-        if self.health <= 0.0 {
+        let destroyed = self.health <= 0.0;
+        if destroyed {
             destroy_events.write(DestroyUapEvent {
                 entity,
                 transform: *transform,
+                source,
+            });
+            score_events.write(ScoreEvent {
+                score_to_add: 1,
+                player: Some(source.player),
             });
-            score_events.write(ScoreEvent { score_to_add: 1 });
-        }
-    }
-}
-
-impl Default for Uap {
-    fn default() -> Self {
-        Self {
-            speed: 200.0,
-            direction: if rand::random::<bool>() { 1.0 } else { -1.0 },
-            margin: 50.0,
-            health: 100.0,
         }
+        destroyed
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct UapAssets {
-    #[dependency]
     uap: Handle<Image>,
+    uap_atlas: Handle<SpriteAtlas>,
 }
 
-impl FromWorld for UapAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            uap: assets.load("images/uap.png"),
-        }
+impl UapAssets {
+    /// A UAP sprite playing its idle animation.
+    pub fn animated_sprite(
+        &self,
+        sprite_atlases: &Assets<SpriteAtlas>,
+    ) -> (Sprite, SpriteAnimator) {
+        // A texture atlas is a way to split a single image into a grid of related images.
+        // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
+        let atlas = sprite_atlases
+            .get(&self.uap_atlas)
+            .expect("the UAP atlas is loaded with the UAP assets");
+        let animator = SpriteAnimator::new(self.uap_atlas.clone(), "idle");
+        let sprite = Sprite {
+            image: self.uap.clone(),
+            texture_atlas: Some(atlas.texture_atlas(animator.atlas_index(atlas))),
+            ..default()
+        };
+        (sprite, animator)
+    }
+}
+
+impl FromAssetManifest for UapAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            uap: manifest.image("uap")?,
+            uap_atlas: manifest.atlas("uap")?,
+        })
+    }
+}
+
+impl VisitAssetDependencies for UapAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.uap.visit_dependencies(visit);
+        self.uap_atlas.visit_dependencies(visit);
+    }
+}
+
+/// Switch between the idling and flying animations, using the damaged variants below half
+/// health.
+fn update_uap_animation(mut uap_query: Query<(&MovementController, &Uap, &mut SpriteAnimator)>) {
+    for (controller, uap, mut animator) in &mut uap_query {
+        let clip = match (controller.intent == Vec2::ZERO, uap.is_damaged()) {
+            (true, false) => "idle",
+            (false, false) => "flying",
+            (true, true) => "idle_damaged",
+            (false, true) => "flying_damaged",
+        };
+        animator.play(clip);
     }
 }
 
-fn uap_movement(
+pub(super) fn uap_movement(
     time: Res<Time>,
-    windows: Query<&Window>,
+    arena: Res<Arena>,
     mut query: Query<(&mut Transform, &mut Uap)>,
 ) {
-    let Ok(window) = windows.single() else {
-        return;
-    };
-    let half_width = window.width() / 2.0;
+    let half_width = arena.half_size().x;
 
     for (mut transform, mut uap) in query.iter_mut() {
         let left_bound = -half_width + uap.margin;
         let right_bound = half_width - uap.margin;
-        let movement = uap.speed * uap.direction * time.delta_secs();
-
-        transform.translation.x += movement;
+        transform.translation += (uap.velocity() * time.delta_secs()).extend(0.0);
 
         if transform.translation.x >= right_bound && uap.direction > 0.0 {
             uap.direction = -1.0;
@@ -153,22 +337,31 @@ fn uap_movement(
 fn handle_destroy_events(
     mut commands: Commands,
     mut destroy_events: EventReader<DestroyUapEvent>,
-    mut materials: ResMut<Assets<SpriteParticle2dMaterial>>,
-    server: Res<AssetServer>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    sprite_query: Query<&Sprite, With<Uap>>,
+    sound_effect_assets: Option<Res<SoundEffectAssets>>,
+    mut rng: ResMut<GameRng>,
 ) {
     for event in destroy_events.read() {
-        let sprite_material = materials.add(SpriteParticle2dMaterial::new(
-            server.load("images/FreePixelFood/Sprite/Food/Pickle.png"),
-            1,
-            1,
-        ));
-
-        commands.spawn((
-            ParticleSpawner(sprite_material),
-            OneShot::Despawn,
-            ParticleEffectHandle(server.load("shaders/uap_explosion.ron")),
-            Transform::from_translation(event.transform.translation),
-        ));
+        if let Ok(sprite) = sprite_query.get(event.entity) {
+            spawn_debris(
+                &mut commands,
+                &layouts,
+                event.entity,
+                sprite,
+                &event.transform,
+                event.source,
+                rng.stream(RngStream::Debris),
+            );
+        }
+
+        if let Some(sound_effect_assets) = &sound_effect_assets {
+            sound_effect_assets.play_uap_destroyed(
+                &mut commands,
+                event.transform.translation,
+                rng.stream(RngStream::Audio),
+            );
+        }
 
         commands.entity(event.entity).despawn();
     }
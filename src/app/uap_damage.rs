@@ -0,0 +1,268 @@
+//! Visible feedback when UAPs are hit: a white flash, knockback away from the blast, smoke
+//! and a damaged look below half health, and a health bar above tough UAPs. The mothership's
+//! parts share the boss health bar instead.
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{AlphaMode2d, Anchor, Material2d, Material2dPlugin},
+};
+use bevy_enoki::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::{boss::BossPart, uap::Uap},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(Material2dPlugin::<HitFlashMaterial>::default());
+    app.add_event::<UapHitEvent>();
+
+    app.register_type::<HitFlash>();
+    app.register_type::<Knockback>();
+    app.add_observer(add_damage_feedback);
+    app.add_systems(
+        FixedUpdate,
+        (handle_hit_events, apply_knockback)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        (update_hit_flash, start_smoking, update_health_bars)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+/// How long a hit flash takes to fade out, in seconds.
+const HIT_FLASH_SECS: f32 = 0.15;
+
+/// The speed a regular UAP is knocked back with, in pixels per second.
+const KNOCKBACK_SPEED: f32 = 350.0;
+
+/// How quickly knockback wears off. Higher values stop the UAP sooner.
+const KNOCKBACK_DAMPING: f32 = 8.0;
+
+const HEALTH_BAR_SIZE: Vec2 = Vec2::new(40.0, 4.0);
+
+/// How far above the UAP's centre the health bar floats.
+const HEALTH_BAR_OFFSET: f32 = 24.0;
+
+/// Sent when a shell hits a UAP.
+#[derive(Event, Debug)]
+pub struct UapHitEvent {
+    pub entity: Entity,
+    /// The direction from the blast towards the UAP.
+    pub direction: Vec2,
+}
+
+/// Draws a sprite sheet frame in a single colour, used to flash UAPs when they're hit.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+struct HitFlashMaterial {
+    #[uniform(0)]
+    color: LinearRgba,
+    /// The frame's UV rectangle in the sheet: min x, min y, max x, max y.
+    #[uniform(1)]
+    uv_rect: Vec4,
+    #[texture(2)]
+    #[sampler(3)]
+    texture: Handle<Image>,
+}
+
+impl Material2d for HitFlashMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/hit_flash.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+/// The fading flash of a recently hit UAP, drawn by its `overlay` child.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct HitFlash {
+    timer: Timer,
+    overlay: Entity,
+}
+
+/// Velocity from being hit, which wears off over time.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct Knockback(Vec2);
+
+/// Marks a UAP that is trailing smoke.
+#[derive(Component)]
+struct Smoking;
+
+#[derive(Component)]
+struct HealthBarFill;
+
+/// Give every UAP a flash overlay, knockback and, for tough ones, a health bar.
+fn add_damage_feedback(
+    trigger: Trigger<OnAdd, Uap>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<HitFlashMaterial>>,
+    uap_query: Query<(&Uap, &Sprite, Has<BossPart>)>,
+) {
+    let Ok((uap, sprite, boss_part)) = uap_query.get(trigger.target()) else {
+        return;
+    };
+
+    let overlay = commands
+        .spawn((
+            Name::new("Hit Flash"),
+            Mesh2d(meshes.add(Rectangle::new(1.0, 1.0))),
+            MeshMaterial2d(materials.add(HitFlashMaterial {
+                color: LinearRgba::NONE,
+                uv_rect: Vec4::new(0.0, 0.0, 1.0, 1.0),
+                texture: sprite.image.clone(),
+            })),
+            Transform::from_xyz(0.0, 0.0, 0.1),
+            Visibility::Hidden,
+            ChildOf(trigger.target()),
+        ))
+        .id();
+
+    let mut timer = Timer::from_seconds(HIT_FLASH_SECS, TimerMode::Once);
+    timer.tick(timer.duration());
+    commands
+        .entity(trigger.target())
+        .insert((HitFlash { timer, overlay }, Knockback::default()));
+
+    if uap.is_tough() && !boss_part {
+        commands.entity(trigger.target()).with_children(|parent| {
+            parent.spawn((
+                Name::new("Health Bar"),
+                Sprite::from_color(Color::srgba(0.0, 0.0, 0.0, 0.6), HEALTH_BAR_SIZE),
+                Transform::from_xyz(0.0, HEALTH_BAR_OFFSET, 0.2),
+            ));
+            parent.spawn((
+                Name::new("Health Bar Fill"),
+                HealthBarFill,
+                Sprite {
+                    color: Color::srgb(0.3, 0.9, 0.3),
+                    custom_size: Some(HEALTH_BAR_SIZE),
+                    anchor: Anchor::CenterLeft,
+                    ..default()
+                },
+                Transform::from_xyz(-HEALTH_BAR_SIZE.x / 2.0, HEALTH_BAR_OFFSET, 0.3),
+            ));
+        });
+    }
+}
+
+/// Flash hit UAPs and knock them away from the blast. Tough UAPs are pushed less, and the parts
+/// of a mothership not at all.
+fn handle_hit_events(
+    mut hit_events: EventReader<UapHitEvent>,
+    mut uap_query: Query<(&Uap, &mut HitFlash, &mut Knockback)>,
+) {
+    for event in hit_events.read() {
+        let Ok((uap, mut flash, mut knockback)) = uap_query.get_mut(event.entity) else {
+            continue;
+        };
+        flash.timer.reset();
+        knockback.0 +=
+            event.direction.normalize_or_zero() * KNOCKBACK_SPEED * uap.knockback_scale();
+    }
+}
+
+/// Fade out the flash, keeping the overlay on the frame the UAP is showing.
+fn update_hit_flash(
+    time: Res<Time>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    mut materials: ResMut<Assets<HitFlashMaterial>>,
+    mut uap_query: Query<(&mut HitFlash, &Sprite)>,
+    mut overlay_query: Query<(
+        &MeshMaterial2d<HitFlashMaterial>,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    for (mut flash, sprite) in &mut uap_query {
+        if flash.timer.finished() {
+            continue;
+        }
+        flash.timer.tick(time.delta());
+
+        let Ok((material, mut transform, mut visibility)) = overlay_query.get_mut(flash.overlay)
+        else {
+            continue;
+        };
+        if flash.timer.finished() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+        material.color = LinearRgba::WHITE.with_alpha(1.0 - flash.timer.fraction());
+
+        let Some(texture_atlas) = &sprite.texture_atlas else {
+            continue;
+        };
+        let Some(layout) = layouts.get(&texture_atlas.layout) else {
+            continue;
+        };
+        let Some(frame) = layout.textures.get(texture_atlas.index) else {
+            continue;
+        };
+        let size = layout.size.as_vec2();
+        material.uv_rect = Vec4::new(
+            frame.min.x as f32 / size.x,
+            frame.min.y as f32 / size.y,
+            frame.max.x as f32 / size.x,
+            frame.max.y as f32 / size.y,
+        );
+        transform.scale = frame.size().as_vec2().extend(1.0);
+    }
+}
+
+fn apply_knockback(time: Res<Time>, mut uap_query: Query<(&mut Transform, &mut Knockback)>) {
+    for (mut transform, mut knockback) in &mut uap_query {
+        if knockback.0 == Vec2::ZERO {
+            continue;
+        }
+        transform.translation += (knockback.0 * time.delta_secs()).extend(0.0);
+        knockback.0 *= (-KNOCKBACK_DAMPING * time.delta_secs()).exp();
+        if knockback.0.length_squared() < 1.0 {
+            knockback.0 = Vec2::ZERO;
+        }
+    }
+}
+
+/// Trail smoke from UAPs below half health.
+fn start_smoking(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    uap_query: Query<(Entity, &Uap), Without<Smoking>>,
+) {
+    for (entity, uap) in &uap_query {
+        if uap.is_damaged() {
+            commands.entity(entity).insert(Smoking).with_child((
+                Name::new("Smoke"),
+                ParticleSpawner::default(),
+                ParticleEffectHandle(server.load("shaders/uap_smoke.particle.ron")),
+                Transform::from_xyz(0.0, 4.0, -0.1),
+            ));
+        }
+    }
+}
+
+fn update_health_bars(
+    uap_query: Query<&Uap, Changed<Uap>>,
+    mut fill_query: Query<(&ChildOf, &mut Sprite), With<HealthBarFill>>,
+) {
+    for (child_of, mut sprite) in &mut fill_query {
+        if let Ok(uap) = uap_query.get(child_of.parent()) {
+            sprite.custom_size = Some(HEALTH_BAR_SIZE * Vec2::new(uap.health_fraction(), 1.0));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Destroyed UAPs break into fragments that tumble under gravity, bounce off the ground and
+//! fade out. Falling fragments can damage other UAPs for a chain bonus, with the flavours of
+//! the blow that broke their UAP apart.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppSystems, PausableSystems,
+    app::{
+        launcher::Launcher,
+        score::ScoreEvent,
+        uap::{DamageSource, DestroyUapEvent, Hitbox, Shielded, Uap},
+        uap_damage::UapHitEvent,
+    },
+    game_rules::GameRules,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DebrisSettings>();
+    app.init_resource::<DebrisSettings>();
+
+    app.register_type::<Fragment>();
+    app.add_systems(
+        FixedUpdate,
+        (move_fragments, fragment_collision, fade_fragments)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Fragments per row and column that a UAP frame is cut into.
+const FRAGMENT_GRID: UVec2 = UVec2::new(4, 2);
+
+/// The fraction of speed a fragment keeps when it bounces off the ground.
+const BOUNCINESS: f32 = 0.45;
+
+/// How long fragments stay around, in seconds.
+const FRAGMENT_LIFETIME_SECS: f32 = 2.5;
+
+/// How long fragments take to fade out at the end of their lifetime, in seconds.
+const FRAGMENT_FADE_SECS: f32 = 1.0;
+
+const FRAGMENT_DAMAGE: f32 = 10.0;
+
+/// Extra points for destroying a UAP with falling debris.
+const CHAIN_BONUS: usize = 2;
+
+/// The ground line used when there's no launcher to take it from.
+const DEFAULT_GROUND_Y: f32 = -450.0;
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct DebrisSettings {
+    /// Whether falling fragments damage the UAPs they pass through.
+    pub chain_damage: bool,
+}
+
+impl Default for DebrisSettings {
+    fn default() -> Self {
+        Self { chain_damage: true }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Fragment {
+    velocity: Vec2,
+    angular_velocity: f32,
+    age: f32,
+    /// The UAP this fragment came from and the ones it has already damaged.
+    hit: Vec<Entity>,
+    /// What destroyed the UAP. Its player scores whatever the fragment brings down, and UAPs'
+    /// defences take its flavours into account.
+    source: DamageSource,
+}
+
+/// Break a destroyed UAP's current frame into fragments flying away from its centre.
+pub fn spawn_debris(
+    commands: &mut Commands,
+    layouts: &Assets<TextureAtlasLayout>,
+    uap: Entity,
+    sprite: &Sprite,
+    transform: &Transform,
+    source: DamageSource,
+    rng: &mut impl Rng,
+) {
+    let Some(frame) = sprite
+        .texture_atlas
+        .as_ref()
+        .and_then(|texture_atlas| texture_atlas.texture_rect(layouts))
+    else {
+        return;
+    };
+    // Fragments are cut from the frame, relative to its top left corner.
+    let frame_size = frame.size().as_vec2();
+    let piece_size = frame_size / FRAGMENT_GRID.as_vec2();
+
+    for y in 0..FRAGMENT_GRID.y {
+        for x in 0..FRAGMENT_GRID.x {
+            let min = piece_size * UVec2::new(x, y).as_vec2();
+            // Offset from the frame centre, with y pointing up.
+            let offset = (min + piece_size / 2.0 - frame_size / 2.0) * Vec2::new(1.0, -1.0);
+            let outwards = offset.normalize_or(Vec2::Y);
+            let velocity = outwards * rng.gen_range(120.0..280.0)
+                + Vec2::new(rng.gen_range(-40.0..40.0), rng.gen_range(150.0..300.0));
+
+            commands.spawn((
+                Name::new("UAP Fragment"),
+                Sprite {
+                    rect: Some(Rect::from_corners(min, min + piece_size)),
+                    ..sprite.clone()
+                },
+                transform.with_translation(transform.translation + offset.extend(0.5)),
+                Fragment {
+                    velocity,
+                    angular_velocity: rng.gen_range(-8.0..8.0),
+                    age: 0.0,
+                    hit: vec![uap],
+                    source,
+                },
+                StateScoped(Screen::Launchpad),
+            ));
+        }
+    }
+}
+
+fn move_fragments(
+    time: Res<Time>,
+    rules: Res<GameRules>,
+    launcher_query: Query<&Transform, (With<Launcher>, Without<Fragment>)>,
+    mut fragment_query: Query<(&mut Transform, &mut Fragment)>,
+) {
+    let ground_y = launcher_query
+        .iter()
+        .map(|transform| transform.translation.y)
+        .reduce(f32::min)
+        .unwrap_or(DEFAULT_GROUND_Y);
+    let dt = time.delta_secs();
+
+    for (mut transform, mut fragment) in &mut fragment_query {
+        fragment.age += dt;
+        fragment.velocity.y -= rules.debris_gravity * dt;
+        transform.translation += (fragment.velocity * dt).extend(0.0);
+        transform.rotate_z(fragment.angular_velocity * dt);
+
+        if transform.translation.y < ground_y && fragment.velocity.y < 0.0 {
+            transform.translation.y = ground_y;
+            fragment.velocity.y = -fragment.velocity.y * BOUNCINESS;
+            fragment.velocity.x *= BOUNCINESS;
+            fragment.angular_velocity *= BOUNCINESS;
+        }
+    }
+}
+
+/// Damage UAPs that falling fragments pass through, once per fragment.
+fn fragment_collision(
+    settings: Res<DebrisSettings>,
+    mut destroy_events: EventWriter<DestroyUapEvent>,
+    mut score_events: EventWriter<ScoreEvent>,
+    mut hit_events: EventWriter<UapHitEvent>,
+    mut fragment_query: Query<(&Transform, &mut Fragment)>,
+    mut uap_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Uap,
+            &Sprite,
+            Option<&Hitbox>,
+            Has<Shielded>,
+        ),
+        Without<Fragment>,
+    >,
+) {
+    if !settings.chain_damage {
+        return;
+    }
+
+    for (fragment_transform, mut fragment) in &mut fragment_query {
+        // Only fragments on their way down do damage.
+        if fragment.velocity.y >= 0.0 {
+            continue;
+        }
+        let fragment_position = fragment_transform.translation.xy();
+
+        for (uap_entity, uap_transform, mut uap, uap_sprite, hitbox, shielded) in &mut uap_query {
+            if fragment.hit.contains(&uap_entity) || uap.health_fraction() <= 0.0 {
+                continue;
+            }
+            let uap_half = Hitbox::size_of(hitbox, uap_sprite) / 2.0;
+            let offset = (fragment_position - uap_transform.translation.xy()).abs();
+            if offset.x > uap_half.x || offset.y > uap_half.y {
+                continue;
+            }
+
+            // Fragments glance off shields and are done with the UAP either way.
+            fragment.hit.push(uap_entity);
+            let Some(damage) =
+                uap.damage_through_defences(FRAGMENT_DAMAGE, &fragment.source.mix, shielded)
+            else {
+                continue;
+            };
+            hit_events.write(UapHitEvent {
+                entity: uap_entity,
+                direction: fragment.velocity,
+            });
+            let destroyed = uap.take_damage(
+                damage,
+                fragment.source,
+                uap_entity,
+                uap_transform,
+                &mut destroy_events,
+                &mut score_events,
+            );
+            if destroyed {
+                score_events.write(ScoreEvent {
+                    score_to_add: CHAIN_BONUS,
+                    player: Some(fragment.source.player),
+                });
+            }
+        }
+    }
+}
+
+fn fade_fragments(
+    mut commands: Commands,
+    mut fragment_query: Query<(Entity, &Fragment, &mut Sprite)>,
+) {
+    for (entity, fragment, mut sprite) in &mut fragment_query {
+        let remaining = FRAGMENT_LIFETIME_SECS - fragment.age;
+        if remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        } else if remaining < FRAGMENT_FADE_SECS {
+            sprite.color.set_alpha(remaining / FRAGMENT_FADE_SECS);
+        }
+    }
+}
@@ -1,39 +1,118 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
+use rand::{Rng, seq::SliceRandom};
 
 use crate::{
-    PausableSystems,
-    app::uap::{Uap, UapAssets, uap},
+    AppSystems, PausableSystems,
+    app::{
+        bits::{DamageTag, Defences},
+        boss::Mothership,
+        game_mode::GameMode,
+        uap::{Uap, UapAssets, uap},
+    },
+    game_rules::GameRules,
+    music::{MusicDirector, MusicMood},
+    rng::{GameRng, RngStream},
     screens::Screen,
+    sprite_atlas::SpriteAtlas,
 };
 
-const MAX_UAPS: usize = 10;
+/// The number of UAPs on screen at which the music switches to its boss wave mood.
+const SWARM_UAPS: usize = 8;
+
+/// Seconds into an endless round at which it's ramped up about two thirds of the way.
+const ENDLESS_RAMP_SECS: f32 = 120.0;
+
+/// How much an endless round ramps up at most.
+const ENDLESS_MIN_SPAWN_SCALE: f32 = 0.4;
+const ENDLESS_EXTRA_UAPS: f32 = 8.0;
+const ENDLESS_MAX_SPEED_SCALE: f32 = 1.8;
+
+/// How fast UAPs climb or sink in endless rounds, in pixels per second, at the start and once
+/// fully ramped up.
+const ENDLESS_DRIFT: (f32, f32) = (15.0, 60.0);
 
 pub(super) fn plugin(app: &mut App) {
     app.insert_resource(UapSpawnTimer {
-        timer: Timer::from_seconds(2.0, TimerMode::Repeating),
+        timer: Timer::from_seconds(GameRules::default().uap_spawn_secs, TimerMode::Repeating),
+        round_secs: 0.0,
     });
+    app.add_systems(OnEnter(Screen::Launchpad), reset_spawn_timer);
+    app.add_systems(
+        FixedUpdate,
+        spawn_uap
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            // The mothership brings its own drones.
+            .run_if(in_state(Screen::Launchpad).and(not(any_with_component::<Mothership>))),
+    );
     app.add_systems(
         Update,
-        ((spawn_uap).chain().run_if(in_state(Screen::Launchpad))).in_set(PausableSystems),
+        update_music_intensity
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Launchpad)),
     );
 }
 
 #[derive(Resource)]
 struct UapSpawnTimer {
     timer: Timer,
+    /// How long the round has been going.
+    round_secs: f32,
+}
+
+/// Start every round with a full spawn interval, so that rounds play out the same way.
+fn reset_spawn_timer(mut spawn_timer: ResMut<UapSpawnTimer>) {
+    spawn_timer.timer.reset();
+    spawn_timer.round_secs = 0.0;
+}
+
+/// How far an endless round has ramped up after `secs`, from 0 at the start towards 1.
+pub fn endless_intensity(secs: f32) -> f32 {
+    1.0 - (-secs / ENDLESS_RAMP_SECS).exp()
+}
+
+/// The rules of an endless round after `secs`, which spawns UAPs faster, more at once and
+/// flying faster the longer it goes on.
+pub fn endless_rules(rules: &GameRules, secs: f32) -> GameRules {
+    let intensity = endless_intensity(secs);
+    let speed_scale = 1.0_f32.lerp(ENDLESS_MAX_SPEED_SCALE, intensity);
+    GameRules {
+        uap_spawn_secs: rules.uap_spawn_secs * 1.0_f32.lerp(ENDLESS_MIN_SPAWN_SCALE, intensity),
+        max_uaps: rules.max_uaps + (ENDLESS_EXTRA_UAPS * intensity).round() as usize,
+        uap_speed: rules.uap_speed * speed_scale,
+        tough_uap_speed: rules.tough_uap_speed * speed_scale,
+        ..rules.clone()
+    }
 }
 
 fn spawn_uap(
     mut commands: Commands,
     time: Res<Time>,
+    rules: Res<GameRules>,
     mut spawn_timer: ResMut<UapSpawnTimer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    sprite_atlases: Res<Assets<SpriteAtlas>>,
     uap_assets: Res<UapAssets>,
     uap_query: Query<&Uap>,
+    mut rng: ResMut<GameRng>,
+    mode: Res<GameMode>,
 ) {
+    spawn_timer.round_secs += time.delta_secs();
+    let endless = *mode == GameMode::Endless;
+    let rules = if endless {
+        endless_rules(&rules, spawn_timer.round_secs)
+    } else {
+        rules.clone()
+    };
+
+    let interval = Duration::from_secs_f32(rules.uap_spawn_secs);
+    if spawn_timer.timer.duration() != interval {
+        spawn_timer.timer.set_duration(interval);
+    }
     spawn_timer.timer.tick(time.delta());
 
-    if spawn_timer.timer.just_finished() && uap_query.iter().len() < MAX_UAPS {
+    if spawn_timer.timer.just_finished() && uap_query.iter().len() < rules.max_uaps {
         commands
             .spawn((
                 Transform::default(),
@@ -41,7 +120,52 @@ fn spawn_uap(
                 StateScoped(Screen::Launchpad),
             ))
             .with_children(|parent| {
-                parent.spawn(uap(400.0, &uap_assets, &mut texture_atlas_layouts));
+                let rng = rng.stream(RngStream::Spawning);
+                let mut kind = if rng.gen_bool(rules.tough_uap_chance.clamp(0.0, 1.0)) {
+                    Uap::tough(&rules, rng)
+                } else {
+                    Uap::regular(&rules, rng)
+                };
+                let defences = roll_defences(&rules, kind.is_tough(), rng);
+                kind = kind.with_defences(defences);
+                if endless {
+                    let (start, end) = ENDLESS_DRIFT;
+                    let drift = start.lerp(end, endless_intensity(spawn_timer.round_secs));
+                    kind = kind.with_drift(if rng.r#gen() { drift } else { -drift });
+                }
+                parent.spawn(uap(400.0, kind, &uap_assets, &sprite_atlases, rng));
             });
     }
 }
+
+/// Shield some UAPs against all but one flavour of bits, and give tough ones a flavour they
+/// resist and one they're weak to.
+fn roll_defences(rules: &GameRules, tough: bool, rng: &mut impl Rng) -> Defences {
+    let mut defences = Defences::default();
+    if rng.gen_bool(rules.shielded_uap_chance.clamp(0.0, 1.0)) {
+        defences.shield = DamageTag::ALL.choose(rng).copied();
+    }
+    if tough {
+        let mut tags = DamageTag::ALL;
+        tags.shuffle(rng);
+        defences.resistance = Some(tags[0]);
+        defences.weakness = Some(tags[1]);
+    }
+    defences
+}
+
+/// Ramp the music up while the launchpad is swarming with UAPs or a mothership is attacking.
+fn update_music_intensity(
+    uap_query: Query<&Uap>,
+    mothership_query: Query<(), With<Mothership>>,
+    mut director: ResMut<MusicDirector>,
+) {
+    let mood = if uap_query.iter().len() >= SWARM_UAPS || !mothership_query.is_empty() {
+        MusicMood::Boss
+    } else {
+        MusicMood::Action
+    };
+    if director.mood != Some(mood) {
+        director.mood = Some(mood);
+    }
+}
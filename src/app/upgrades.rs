@@ -0,0 +1,168 @@
+//! Launcher upgrades bought in the workshop with credits earned on the launchpad, saved between
+//! sessions.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems,
+    app::{replay::ReplayPlayback, score::ScoreEvent},
+    persistence,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Progress>();
+    app.insert_resource(persistence::load::<Progress>(PROGRESS_FILE).unwrap_or_default());
+
+    // Replayed rounds neither earn credits nor touch the saved progress.
+    app.add_systems(
+        Update,
+        earn_credits
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Launchpad).and(not(resource_exists::<ReplayPlayback>))),
+    );
+    app.add_systems(
+        OnExit(Screen::Launchpad),
+        save_progress.run_if(not(resource_exists::<ReplayPlayback>)),
+    );
+}
+
+const PROGRESS_FILE: &str = "progress.ron";
+
+/// The credits the first level of an upgrade costs. Every level after costs as much again.
+const UPGRADE_COST: usize = 25;
+
+/// Something about the launchers that can be improved in the workshop.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Upgrade {
+    Rotation,
+    Aim,
+    MuzzleVelocity,
+    DoubleBarrel,
+    Reload,
+}
+
+impl Upgrade {
+    pub const ALL: [Self; 5] = [
+        Self::Rotation,
+        Self::Aim,
+        Self::MuzzleVelocity,
+        Self::DoubleBarrel,
+        Self::Reload,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rotation => "Faster turning",
+            Self::Aim => "Wider aim",
+            Self::MuzzleVelocity => "Muzzle velocity",
+            Self::DoubleBarrel => "Double barrel",
+            Self::Reload => "Faster reload",
+        }
+    }
+
+    pub fn max_level(self) -> u8 {
+        match self {
+            Self::DoubleBarrel => 1,
+            _ => 3,
+        }
+    }
+}
+
+/// The level of every upgrade, where 0 is the launcher as it comes.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct LauncherUpgrades {
+    pub rotation: u8,
+    pub aim: u8,
+    pub muzzle_velocity: u8,
+    pub double_barrel: u8,
+    pub reload: u8,
+}
+
+impl LauncherUpgrades {
+    pub fn level(&self, upgrade: Upgrade) -> u8 {
+        match upgrade {
+            Upgrade::Rotation => self.rotation,
+            Upgrade::Aim => self.aim,
+            Upgrade::MuzzleVelocity => self.muzzle_velocity,
+            Upgrade::DoubleBarrel => self.double_barrel,
+            Upgrade::Reload => self.reload,
+        }
+    }
+
+    fn level_mut(&mut self, upgrade: Upgrade) -> &mut u8 {
+        match upgrade {
+            Upgrade::Rotation => &mut self.rotation,
+            Upgrade::Aim => &mut self.aim,
+            Upgrade::MuzzleVelocity => &mut self.muzzle_velocity,
+            Upgrade::DoubleBarrel => &mut self.double_barrel,
+            Upgrade::Reload => &mut self.reload,
+        }
+    }
+
+    /// How many times faster than the rules' speed the launcher turns.
+    pub fn rotation_speed_scale(&self) -> f32 {
+        1.0 + 0.2 * self.rotation as f32
+    }
+
+    /// How much further than the rules allow the launcher turns either way, in radians.
+    pub fn extra_aim_angle(&self) -> f32 {
+        0.1 * self.aim as f32
+    }
+
+    /// How many times faster than the rules' speed shells leave the launcher.
+    pub fn muzzle_speed_scale(&self) -> f32 {
+        1.0 + 0.15 * self.muzzle_velocity as f32
+    }
+
+    /// How many shells the launcher fires at once.
+    pub fn barrels(&self) -> usize {
+        1 + self.double_barrel as usize
+    }
+
+    /// The share of the rules' reload time the launcher takes to reload.
+    pub fn reload_scale(&self) -> f32 {
+        1.0 - 0.2 * self.reload as f32
+    }
+}
+
+/// The player's credits and the upgrades they've bought.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Progress {
+    pub credits: usize,
+    pub upgrades: LauncherUpgrades,
+}
+
+impl Progress {
+    /// What the next level of an upgrade costs, or `None` if it's fully upgraded.
+    pub fn cost(&self, upgrade: Upgrade) -> Option<usize> {
+        let level = self.upgrades.level(upgrade);
+        (level < upgrade.max_level()).then_some(UPGRADE_COST * (level as usize + 1))
+    }
+
+    /// Buy the next level of an upgrade if there are credits enough, and save the purchase.
+    pub fn buy(&mut self, upgrade: Upgrade) -> bool {
+        let Some(cost) = self.cost(upgrade).filter(|&cost| cost <= self.credits) else {
+            return false;
+        };
+        self.credits -= cost;
+        *self.upgrades.level_mut(upgrade) += 1;
+        persistence::save(PROGRESS_FILE, self);
+        true
+    }
+}
+
+/// Every point scored is a credit to spend in the workshop.
+fn earn_credits(mut score_events: EventReader<ScoreEvent>, mut progress: ResMut<Progress>) {
+    for event in score_events.read() {
+        progress.credits += event.score_to_add;
+    }
+}
+
+fn save_progress(progress: Res<Progress>) {
+    persistence::save(PROGRESS_FILE, &*progress);
+}
@@ -0,0 +1,215 @@
+//! A RON manifest mapping logical asset keys to files and their loader settings, so sprites and
+//! sounds can be added or swapped without recompiling.
+//!
+//! The manifest of the selected theme is loaded from `assets/themes/<theme>.assets.ron`. A theme
+//! can name a `base` theme and only list the assets it replaces. Asset collections are built from
+//! the manifest with [`FromAssetManifest`] and requested with
+//! [`LoadResource::load_resource`](crate::asset_tracking::LoadResource::load_resource).
+
+use std::{collections::HashMap, error::Error, fmt};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, UntypedAssetId, VisitAssetDependencies, io::Reader},
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{cli, sprite_atlas::SpriteAtlas};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<AssetManifest>();
+    app.init_asset_loader::<AssetManifestLoader>();
+
+    let path = theme_path(&selected_theme());
+    let handle = app.world().resource::<AssetServer>().load(path);
+    app.insert_resource(AssetManifestHandle(handle));
+}
+
+/// The theme used unless another one is passed with `--theme <name>`.
+const DEFAULT_THEME: &str = "default";
+
+fn selected_theme() -> String {
+    cli::value("--theme").unwrap_or_else(|| DEFAULT_THEME.to_string())
+}
+
+fn theme_path(theme: &str) -> String {
+    format!("themes/{theme}.assets.ron")
+}
+
+/// The manifest of the selected theme.
+#[derive(Resource, Debug)]
+pub struct AssetManifestHandle(pub Handle<AssetManifest>);
+
+/// Asset handles by their logical key. All of them are dependencies of the manifest, so it
+/// only counts as loaded once every listed file has loaded.
+#[derive(TypePath, Debug, Default)]
+pub struct AssetManifest {
+    images: HashMap<String, Handle<Image>>,
+    atlases: HashMap<String, Handle<SpriteAtlas>>,
+    /// Sounds can have several variants, one of which is picked each time they play.
+    audio: HashMap<String, Vec<Handle<AudioSource>>>,
+}
+
+impl Asset for AssetManifest {}
+
+impl VisitAssetDependencies for AssetManifest {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        for handle in self.images.values() {
+            visit(handle.id().untyped());
+        }
+        for handle in self.atlases.values() {
+            visit(handle.id().untyped());
+        }
+        for handle in self.audio.values().flatten() {
+            visit(handle.id().untyped());
+        }
+    }
+}
+
+impl AssetManifest {
+    pub fn image(&self, key: &str) -> Result<Handle<Image>, MissingAssetKey> {
+        self.images
+            .get(key)
+            .cloned()
+            .ok_or_else(|| MissingAssetKey(key.to_string()))
+    }
+
+    pub fn atlas(&self, key: &str) -> Result<Handle<SpriteAtlas>, MissingAssetKey> {
+        self.atlases
+            .get(key)
+            .cloned()
+            .ok_or_else(|| MissingAssetKey(key.to_string()))
+    }
+
+    /// The first variant of a sound.
+    pub fn audio(&self, key: &str) -> Result<Handle<AudioSource>, MissingAssetKey> {
+        self.audio_variants(key)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| MissingAssetKey(key.to_string()))
+    }
+
+    pub fn audio_variants(&self, key: &str) -> Result<Vec<Handle<AudioSource>>, MissingAssetKey> {
+        self.audio
+            .get(key)
+            .cloned()
+            .ok_or_else(|| MissingAssetKey(key.to_string()))
+    }
+}
+
+/// A collection of asset handles that can be looked up in the [`AssetManifest`]. The handles
+/// it visits are the files it waits on, so that a failed file is only blamed on the collections
+/// that hold it.
+pub trait FromAssetManifest: VisitAssetDependencies + Sized {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey>;
+}
+
+/// A key that was requested from the [`AssetManifest`] but isn't listed in it.
+#[derive(Debug, Clone)]
+pub struct MissingAssetKey(pub String);
+
+impl fmt::Display for MissingAssetKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" is missing from the asset manifest", self.0)
+    }
+}
+
+impl Error for MissingAssetKey {}
+
+/// The contents of a `.assets.ron` file.
+#[derive(Deserialize)]
+struct ManifestFile {
+    /// The theme to take any assets not listed here from.
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    images: HashMap<String, ImageEntry>,
+    /// Paths to `.atlas.ron` files describing the frames of sprite sheets.
+    #[serde(default)]
+    atlases: HashMap<String, String>,
+    #[serde(default)]
+    audio: HashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct ImageEntry {
+    path: String,
+    #[serde(default)]
+    sampler: Sampler,
+}
+
+/// The image sampling to use, overriding the default set on `ImagePlugin`.
+#[derive(Deserialize, Default, Clone, Copy)]
+enum Sampler {
+    #[default]
+    Default,
+    Nearest,
+    Linear,
+}
+
+#[derive(Default)]
+struct AssetManifestLoader;
+
+impl AssetLoader for AssetManifestLoader {
+    type Asset = AssetManifest;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<AssetManifest, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file = ron::de::from_bytes::<ManifestFile>(&bytes)?;
+
+        let mut manifest = match &file.base {
+            Some(base) => load_context
+                .loader()
+                .immediate()
+                .load::<AssetManifest>(theme_path(base))
+                .await?
+                .take(),
+            None => AssetManifest::default(),
+        };
+
+        for (key, entry) in file.images {
+            let sampler = match entry.sampler {
+                Sampler::Default => None,
+                Sampler::Nearest => Some(ImageSampler::nearest()),
+                Sampler::Linear => Some(ImageSampler::linear()),
+            };
+            let handle = match sampler {
+                Some(sampler) => load_context
+                    .loader()
+                    .with_settings(move |settings: &mut ImageLoaderSettings| {
+                        settings.sampler = sampler.clone();
+                    })
+                    .load(entry.path),
+                None => load_context.load(entry.path),
+            };
+            manifest.images.insert(key, handle);
+        }
+
+        for (key, path) in file.atlases {
+            manifest.atlases.insert(key, load_context.load(path));
+        }
+
+        for (key, paths) in file.audio {
+            let handles = paths
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect();
+            manifest.audio.insert(key, handles);
+        }
+
+        Ok(manifest)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["assets.ron"]
+    }
+}
@@ -1,71 +1,418 @@
 //! A high-level way to load collections of asset handles as resources.
 
-use std::collections::VecDeque;
+use std::{
+    any::{TypeId, type_name},
+    sync::Arc,
+};
 
-use bevy::prelude::*;
+use bevy::{
+    asset::{
+        AssetLoadError, LoadState, RecursiveDependencyLoadState, RenderAssetUsages, UntypedAssetId,
+    },
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::asset_manifest::{
+    AssetManifest, AssetManifestHandle, FromAssetManifest, MissingAssetKey,
+};
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_event::<ResourceLoadFailed>();
     app.init_resource::<ResourceHandles>();
+    app.insert_resource(SubstitutePlaceholders(cfg!(not(feature = "dev"))));
     app.add_systems(PreUpdate, load_resource_assets);
 }
 
+/// Whether assets that fail to load should be replaced with placeholders (a magenta checkerboard
+/// for images, silence for audio) so the game remains playable. Only images and audio can be
+/// substituted; resources depending on other failed assets still fail.
+///
+/// This is disabled in dev builds, so that broken assets are impossible to miss.
+#[derive(Resource, Debug)]
+pub struct SubstitutePlaceholders(pub bool);
+
+/// Sent when some assets of a [`Resource`] requested with [`LoadResource::load_resource`] failed
+/// to load.
+#[derive(Event, Debug, Clone)]
+pub struct ResourceLoadFailed {
+    /// The human-readable name of the resource.
+    pub resource: String,
+    /// The paths of the asset files that failed to load, or the keys missing from the manifest.
+    pub paths: Vec<String>,
+    /// Whether the failed assets were replaced with placeholders, in which case the resource
+    /// is available anyway.
+    pub substituted: bool,
+}
+
 pub trait LoadResource {
-    /// This will load the [`Resource`] as an [`Asset`]. When all of its asset dependencies
-    /// have been loaded, it will be inserted as a resource. This ensures that the resource only
-    /// exists when the assets are ready.
-    fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self;
+    /// This will look up the [`Resource`] in the [`AssetManifest`]. When the manifest and all of
+    /// its assets have been loaded, it will be inserted as a resource. This ensures that the
+    /// resource only exists when the assets are ready.
+    fn load_resource<T: Resource + Clone + FromAssetManifest>(&mut self) -> &mut Self;
 }
 
 impl LoadResource for App {
-    fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self {
-        self.init_asset::<T>();
+    fn load_resource<T: Resource + Clone + FromAssetManifest>(&mut self) -> &mut Self {
         let world = self.world_mut();
-        let value = T::from_world(world);
-        let assets = world.resource::<AssetServer>();
-        let handle = assets.add(value);
+        assert!(
+            world.contains_resource::<AssetManifestHandle>(),
+            "the asset manifest plugin must be added before loading resources"
+        );
         let mut handles = world.resource_mut::<ResourceHandles>();
-        handles
-            .waiting
-            .push_back((handle.untyped(), |world, handle| {
-                let assets = world.resource::<Assets<T>>();
-                if let Some(value) = assets.get(handle.id().typed::<T>()) {
-                    world.insert_resource(value.clone());
-                }
-            }));
+        handles.resources.push(TrackedResource {
+            name: resource_name::<T>(),
+            state: ResourceLoadState::Waiting,
+            dependencies: None,
+            loaded: 0,
+            find_dependencies: |manifest| {
+                let mut dependencies = Vec::new();
+                T::from_manifest(manifest)?.visit_dependencies(&mut |id| dependencies.push(id));
+                Ok(dependencies)
+            },
+            insert: |world, manifest| {
+                let value = T::from_manifest(manifest)?;
+                world.insert_resource(value);
+                Ok(())
+            },
+        });
         self
     }
 }
 
-/// A function that inserts a loaded resource.
-type InsertLoadedResource = fn(&mut World, &UntypedHandle);
+/// A human-readable name for a resource type, e.g. "launcher crank" for `LauncherCrankAssets`.
+fn resource_name<T>() -> String {
+    let name = type_name::<T>().rsplit("::").next().unwrap_or_default();
+    let name = name.strip_suffix("Assets").unwrap_or(name);
+
+    let mut words = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            words.push(' ');
+        }
+        words.extend(c.to_lowercase());
+    }
+    words
+}
+
+/// A function that builds a resource from the loaded manifest and lists the assets it holds.
+type FindDependencies = fn(&AssetManifest) -> Result<Vec<UntypedAssetId>, MissingAssetKey>;
+
+/// A function that builds a resource from the loaded manifest and inserts it.
+type InsertLoadedResource = fn(&mut World, &AssetManifest) -> Result<(), MissingAssetKey>;
+
+/// The load state of a resource requested with [`LoadResource::load_resource`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceLoadState {
+    /// Some of its assets are still loading.
+    Waiting,
+    /// All of its assets have loaded and it's available as a [`Resource`].
+    Loaded,
+    /// One or more of its assets failed to load, so it will never be available.
+    Failed,
+}
+
+struct TrackedResource {
+    name: String,
+    state: ResourceLoadState,
+    /// The assets the resource holds, once the manifest has been read.
+    dependencies: Option<Vec<UntypedAssetId>>,
+    /// How many of its dependencies have loaded.
+    loaded: usize,
+    find_dependencies: FindDependencies,
+    insert: InsertLoadedResource,
+}
 
 #[derive(Resource, Default)]
 pub struct ResourceHandles {
-    // Use a queue for waiting assets so they can be cycled through and moved to
-    // `finished` one at a time.
-    waiting: VecDeque<(UntypedHandle, InsertLoadedResource)>,
-    finished: Vec<UntypedHandle>,
+    resources: Vec<TrackedResource>,
+    /// Whether the manifest itself failed to load, which has been reported once for all of the
+    /// resources.
+    manifest_failed: bool,
 }
 
 impl ResourceHandles {
     /// Returns true if all requested [`Asset`]s have finished loading and are available as [`Resource`]s.
     pub fn is_all_done(&self) -> bool {
-        self.waiting.is_empty()
+        self.resources
+            .iter()
+            .all(|resource| resource.state == ResourceLoadState::Loaded)
+    }
+
+    /// Returns true if any requested [`Resource`] failed to load.
+    pub fn has_failed(&self) -> bool {
+        self.resources
+            .iter()
+            .any(|resource| resource.state == ResourceLoadState::Failed)
+    }
+
+    /// The name and load state of every requested [`Resource`].
+    pub fn states(&self) -> impl Iterator<Item = (&str, ResourceLoadState)> {
+        self.resources
+            .iter()
+            .map(|resource| (resource.name.as_str(), resource.state))
+    }
+
+    /// The name of a [`Resource`] that is still loading, if any.
+    pub fn currently_loading(&self) -> Option<&str> {
+        self.states()
+            .find(|(_, state)| *state == ResourceLoadState::Waiting)
+            .map(|(name, _)| name)
+    }
+
+    /// The fraction of asset handles that have finished loading, between 0 and 1.
+    pub fn progress(&self) -> f32 {
+        let (loaded, total) = self
+            .resources
+            .iter()
+            .map(|resource| {
+                // Count resources without any dependencies as a single handle.
+                let total = resource.dependencies.as_ref().map_or(0, Vec::len).max(1);
+                match resource.state {
+                    ResourceLoadState::Loaded => (total, total),
+                    _ => (resource.loaded, total),
+                }
+            })
+            .fold((0, 0), |(a, b), (c, d)| (a + c, b + d));
+
+        if total == 0 {
+            1.0
+        } else {
+            loaded as f32 / total as f32
+        }
     }
 }
 
 fn load_resource_assets(world: &mut World) {
+    let manifest_handle = world.resource::<AssetManifestHandle>().0.clone();
     world.resource_scope(|world, mut resource_handles: Mut<ResourceHandles>| {
-        world.resource_scope(|world, assets: Mut<AssetServer>| {
-            for _ in 0..resource_handles.waiting.len() {
-                let (handle, insert_fn) = resource_handles.waiting.pop_front().unwrap();
-                if assets.is_loaded_with_dependencies(&handle) {
-                    insert_fn(world, &handle);
-                    resource_handles.finished.push(handle);
-                } else {
-                    resource_handles.waiting.push_back((handle, insert_fn));
-                }
+        if resource_handles.manifest_failed {
+            return;
+        }
+        match world.resource::<AssetServer>().load_state(&manifest_handle) {
+            LoadState::Loaded => {}
+            LoadState::Failed(error) => {
+                fail_manifest(world, &mut resource_handles, &manifest_handle, &error);
+                return;
             }
-        });
+            _ => return,
+        }
+
+        for resource in &mut resource_handles.resources {
+            if resource.state != ResourceLoadState::Waiting {
+                continue;
+            }
+            let manifests = world.resource::<Assets<AssetManifest>>();
+            let Some(manifest) = manifests.get(&manifest_handle) else {
+                continue;
+            };
+            let dependencies = match &resource.dependencies {
+                Some(dependencies) => dependencies,
+                None => match (resource.find_dependencies)(manifest) {
+                    Ok(dependencies) => resource.dependencies.insert(dependencies),
+                    Err(missing) => {
+                        fail_missing_key(world, resource, missing);
+                        continue;
+                    }
+                },
+            };
+
+            let server = world.resource::<AssetServer>();
+            let states = dependencies
+                .iter()
+                .map(|id| server.get_recursive_dependency_load_state(*id))
+                .collect::<Vec<_>>();
+            resource.loaded = states
+                .iter()
+                .filter(|state| matches!(state, Some(RecursiveDependencyLoadState::Loaded)))
+                .count();
+            // Wait for every file to settle, so that all of the failures are reported together.
+            let failed = dependencies
+                .iter()
+                .zip(&states)
+                .filter(|(_, state)| state.as_ref().is_some_and(|state| state.is_failed()))
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            if resource.loaded + failed.len() < dependencies.len() {
+                continue;
+            }
+
+            if failed.is_empty() {
+                insert_loaded_resource(world, resource, &manifest_handle);
+            } else {
+                handle_failed_resource(world, resource, &manifest_handle, &failed);
+            }
+        }
+    });
+}
+
+/// Report that the manifest couldn't be read, once, and give up on every resource listed in it.
+fn fail_manifest(
+    world: &mut World,
+    resource_handles: &mut ResourceHandles,
+    handle: &Handle<AssetManifest>,
+    error: &AssetLoadError,
+) {
+    let path = world
+        .resource::<AssetServer>()
+        .get_path(handle)
+        .map_or_else(|| format!("{handle:?}"), |path| path.to_string());
+    error!("Failed to load the asset manifest {path}: {error}");
+    resource_handles.manifest_failed = true;
+    for resource in &mut resource_handles.resources {
+        resource.state = ResourceLoadState::Failed;
+    }
+    world.send_event(ResourceLoadFailed {
+        resource: "theme".to_string(),
+        paths: vec![path],
+        substituted: false,
+    });
+}
+
+/// Fail a resource that needs a key missing from the manifest.
+fn fail_missing_key(world: &mut World, resource: &mut TrackedResource, missing: MissingAssetKey) {
+    error!("The {} assets are unavailable: {missing}", resource.name);
+    resource.state = ResourceLoadState::Failed;
+    world.send_event(ResourceLoadFailed {
+        resource: resource.name.clone(),
+        paths: vec![missing.0],
+        substituted: false,
+    });
+}
+
+/// Insert a resource whose assets are available, or fail it if it needs keys that are missing
+/// from the manifest. Returns true if the resource was inserted.
+fn insert_loaded_resource(
+    world: &mut World,
+    resource: &mut TrackedResource,
+    manifest: &Handle<AssetManifest>,
+) -> bool {
+    let result = world.resource_scope(|world, manifests: Mut<Assets<AssetManifest>>| {
+        let manifest = manifests
+            .get(manifest)
+            .expect("resources are only inserted once the manifest has loaded");
+        (resource.insert)(world, manifest)
+    });
+    match result {
+        Ok(()) => {
+            resource.state = ResourceLoadState::Loaded;
+            true
+        }
+        Err(missing) => {
+            fail_missing_key(world, resource, missing);
+            false
+        }
+    }
+}
+
+/// Log which files of a resource failed to load and substitute placeholders for them if enabled,
+/// inserting the resource anyway if all of them could be replaced.
+fn handle_failed_resource(
+    world: &mut World,
+    resource: &mut TrackedResource,
+    manifest: &Handle<AssetManifest>,
+    failed: &[UntypedAssetId],
+) {
+    let server = world.resource::<AssetServer>();
+    let mut paths = Vec::new();
+    for id in failed {
+        let path = server
+            .get_path(*id)
+            .map_or_else(|| format!("{id:?}"), |path| path.to_string());
+        match server.load_state(*id) {
+            LoadState::Failed(error) => error!("Failed to load {path}: {error}"),
+            _ => error!("Failed to load a dependency of {path}"),
+        }
+        paths.push(path);
+    }
+
+    let substituted = world.resource::<SubstitutePlaceholders>().0
+        && failed.iter().all(|id| substitute_placeholder(world, *id));
+    world.send_event(ResourceLoadFailed {
+        resource: resource.name.clone(),
+        paths,
+        substituted,
     });
+
+    if !substituted {
+        error!("The {} assets are unavailable", resource.name);
+        resource.state = ResourceLoadState::Failed;
+    } else if insert_loaded_resource(world, resource, manifest) {
+        warn!("Using placeholders for the {} assets", resource.name);
+    }
+}
+
+/// Replace a failed asset with a placeholder. Returns false if there is no placeholder for its
+/// asset type.
+fn substitute_placeholder(world: &mut World, id: UntypedAssetId) -> bool {
+    if id.type_id() == TypeId::of::<Image>() {
+        world
+            .resource_mut::<Assets<Image>>()
+            .insert(id.typed::<Image>(), placeholder_image());
+        true
+    } else if id.type_id() == TypeId::of::<AudioSource>() {
+        world
+            .resource_mut::<Assets<AudioSource>>()
+            .insert(id.typed::<AudioSource>(), placeholder_audio());
+        true
+    } else {
+        false
+    }
+}
+
+/// A magenta and black checkerboard, the classic "missing texture".
+fn placeholder_image() -> Image {
+    const SIZE: u32 = 16;
+    const CHECKER_SIZE: u32 = 4;
+
+    let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let magenta = (x / CHECKER_SIZE + y / CHECKER_SIZE).is_multiple_of(2);
+            data.extend_from_slice(if magenta {
+                &[255, 0, 255, 255]
+            } else {
+                &[0, 0, 0, 255]
+            });
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// A short, silent mono WAV file.
+fn placeholder_audio() -> AudioSource {
+    const SAMPLE_RATE: u32 = 8000;
+    const SAMPLES: u32 = 800;
+
+    let data_size = SAMPLES * 2;
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    // Format chunk: 16 bytes of uncompressed PCM, 1 channel, 16 bits per sample.
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    bytes.resize(44 + data_size as usize, 0);
+
+    AudioSource {
+        bytes: Arc::from(bytes),
+    }
 }
@@ -1,15 +1,49 @@
-use bevy::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+use rand::Rng;
+
+use crate::Pause;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Music>();
     app.register_type::<SoundEffect>();
+    app.register_type::<PositionalAudio>();
+    app.register_type::<AttenuationOrigin>();
+
+    app.register_type::<AudioOutput>();
+    app.init_resource::<AudioOutput>();
 
+    app.add_observer(configure_positional_audio);
     app.add_systems(
         Update,
-        apply_global_volume.run_if(resource_changed::<GlobalVolume>),
+        (
+            apply_global_volume.run_if(resource_changed::<GlobalVolume>),
+            apply_distance_attenuation,
+            pause_positional_audio.run_if(state_changed::<Pause>),
+        ),
     );
 }
 
+/// The world-space to audio-space scale. Sounds at the horizontal edges of a 1920 px wide
+/// window end up right next to one of the listener's ears.
+pub const AUDIO_SCALE: f32 = 1.0 / 960.0;
+
+/// The gap between the ears of the [`SpatialListener`] in audio space.
+pub const LISTENER_EAR_GAP: f32 = 2.0;
+
+/// The distance in world units at which positional sounds are played at half volume.
+const ATTENUATION_HALF_DISTANCE: f32 = 800.0;
+
+/// How audio should be played back on the player's speakers.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub enum AudioOutput {
+    /// Pan positional sounds by their position on screen.
+    #[default]
+    Stereo,
+    /// Play every sound centered, e.g. for single-speaker setups.
+    Mono,
+}
+
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
 /// general "music" category (e.g. global background music, soundtrack).
 ///
@@ -36,10 +70,136 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
+/// A sound effect audio instance with its pitch and volume randomly nudged by up to
+/// `variation` (as a fraction), so that frequently repeated sounds don't become monotonous.
+pub fn sound_effect_varied(
+    handle: Handle<AudioSource>,
+    pitch: f32,
+    volume: f32,
+    variation: f32,
+    rng: &mut impl Rng,
+) -> impl Bundle {
+    let pitch = pitch * (1.0 + variation * rng.gen_range(-1.0..=1.0));
+    let volume = volume * (1.0 + variation * rng.gen_range(-1.0..=1.0));
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_speed(pitch)
+            .with_volume(Volume::Linear(volume.max(0.0))),
+        SoundEffect,
+    )
+}
+
+/// A component for sounds that are emitted from a point in the world. These are panned
+/// by their on-screen position (unless [`AudioOutput::Mono`] is selected) and get quieter the
+/// further they are from the nearest [`AttenuationOrigin`].
+///
+/// The sound's position is taken from its [`GlobalTransform`], so it can be a child of a
+/// moving entity to follow it around.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct PositionalAudio {
+    /// The volume of the sound before distance attenuation is applied.
+    base_volume: f32,
+}
+
+/// Makes a sound effect (or any other audio bundle) positional at the given world position.
+pub fn positional(position: Vec3) -> impl Bundle {
+    (
+        Transform::from_translation(position),
+        PositionalAudio::default(),
+    )
+}
+
+/// A marker component for the point positional sounds are attenuated from, e.g. the launcher the
+/// player is operating.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct AttenuationOrigin;
+
+/// Volume multiplier of a positional sound at the given position.
+fn attenuation(position: Vec3, origins: &Query<&GlobalTransform, With<AttenuationOrigin>>) -> f32 {
+    origins
+        .iter()
+        .map(|origin| origin.translation().xy().distance(position.xy()))
+        .min_by(f32::total_cmp)
+        .map_or(1.0, |distance| {
+            1.0 / (1.0 + distance / ATTENUATION_HALF_DISTANCE)
+        })
+}
+
+/// Enable spatial playback for new positional sounds and attenuate their starting volume.
+fn configure_positional_audio(
+    trigger: Trigger<OnAdd, PositionalAudio>,
+    audio_output: Res<AudioOutput>,
+    origins: Query<&GlobalTransform, With<AttenuationOrigin>>,
+    mut audio_query: Query<(&mut PlaybackSettings, &mut PositionalAudio, &Transform)>,
+) {
+    let Ok((mut playback, mut positional, transform)) = audio_query.get_mut(trigger.target())
+    else {
+        return;
+    };
+
+    positional.base_volume = playback.volume.to_linear();
+    playback.spatial = *audio_output == AudioOutput::Stereo;
+    // The global transform hasn't been propagated yet, so this is only exact for root entities.
+    // Children are corrected by `apply_distance_attenuation` once they start playing.
+    playback.volume =
+        Volume::Linear(positional.base_volume * attenuation(transform.translation, &origins));
+}
+
+/// Keep the volume of positional sounds in sync with their distance to the attenuation origin.
+fn apply_distance_attenuation(
+    global_volume: Res<GlobalVolume>,
+    origins: Query<&GlobalTransform, With<AttenuationOrigin>>,
+    mut audio_query: Query<(
+        &PositionalAudio,
+        &GlobalTransform,
+        Option<&mut AudioSink>,
+        Option<&mut SpatialAudioSink>,
+    )>,
+) {
+    for (positional, transform, sink, spatial_sink) in &mut audio_query {
+        let volume = global_volume.volume
+            * Volume::Linear(
+                positional.base_volume * attenuation(transform.translation(), &origins),
+            );
+        if let Some(mut sink) = sink {
+            sink.set_volume(volume);
+        }
+        if let Some(mut sink) = spatial_sink {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+/// Hold positional sounds, which all come from the game world, while the game is paused. Sounds
+/// that aren't positional, like the menus' clicks, keep playing.
+fn pause_positional_audio(
+    pause: Res<State<Pause>>,
+    audio_query: Query<(Option<&AudioSink>, Option<&SpatialAudioSink>), With<PositionalAudio>>,
+) {
+    let paused = pause.get().0;
+    for (sink, spatial_sink) in &audio_query {
+        if let Some(sink) = sink {
+            set_paused(sink, paused);
+        }
+        if let Some(sink) = spatial_sink {
+            set_paused(sink, paused);
+        }
+    }
+}
+
+fn set_paused(sink: &impl AudioSinkPlayback, paused: bool) {
+    if sink.is_paused() != paused {
+        if paused { sink.pause() } else { sink.play() }
+    }
+}
+
 /// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
 fn apply_global_volume(
     global_volume: Res<GlobalVolume>,
-    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink)>,
+    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink), Without<PositionalAudio>>,
 ) {
     for (playback, mut sink) in &mut audio_query {
         sink.set_volume(global_volume.volume * playback.volume);
@@ -0,0 +1,26 @@
+//! Reading options from the command line, such as `--seed 7` or `--seed=7`.
+
+/// Whether the option `name` was passed, with or without a value.
+pub fn is_set(name: &str) -> bool {
+    std::env::args().any(|arg| arg == name || inline_value(&arg, name).is_some())
+}
+
+/// The value passed after the option `name`, or `None` if the option wasn't passed or is the
+/// last argument.
+pub fn value(name: &str) -> Option<String> {
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = inline_value(&arg, name) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// The value of `arg` if it's the option `name` with its value attached, as in `--seed=7`.
+fn inline_value<'a>(arg: &'a str, name: &str) -> Option<&'a str> {
+    arg.strip_prefix(name)?.strip_prefix('=')
+}
@@ -0,0 +1,248 @@
+//! Gameplay tuning loaded from `assets/rules/game.rules.ron`, with a preset for each
+//! [`Difficulty`].
+//!
+//! Systems read the active preset from the [`GameRules`] resource. It starts out with the
+//! Normal values and is replaced once the rules file has loaded, whenever another difficulty is
+//! picked, and whenever the file changes on disk in builds with hot reloading. A
+//! [`RulesModifier`] can put a twist on whichever preset is active.
+
+use std::{collections::HashMap, error::Error};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<GameRulesAsset>();
+    app.init_asset_loader::<GameRulesLoader>();
+
+    app.register_type::<Difficulty>();
+    app.register_type::<GameRules>();
+    app.register_type::<RulesModifier>();
+    app.init_resource::<Difficulty>();
+    app.init_resource::<GameRules>();
+    app.init_resource::<RulesModifier>();
+
+    let handle = app.world().resource::<AssetServer>().load(RULES_PATH);
+    app.insert_resource(GameRulesHandle(handle));
+
+    app.add_systems(Update, apply_game_rules);
+}
+
+const RULES_PATH: &str = "rules/game.rules.ron";
+
+/// The preset the [`GameRules`] are taken from.
+#[derive(
+    Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// Values for players to tweak in the rules file.
+    Custom,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 4] = [Self::Easy, Self::Normal, Self::Hard, Self::Custom];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&d| d == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&d| d == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Custom => "Custom",
+        }
+    }
+}
+
+/// The tuning of a round.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+pub struct GameRules {
+    /// The most UAPs on the launchpad at once.
+    pub max_uaps: usize,
+    /// Seconds between UAP spawns.
+    pub uap_spawn_secs: f32,
+    /// The speed of a regular UAP, in pixels per second.
+    pub uap_speed: f32,
+    pub uap_health: f32,
+    /// The chance that a newly spawned UAP is a tough one.
+    pub tough_uap_chance: f64,
+    pub tough_uap_speed: f32,
+    pub tough_uap_health: f32,
+    /// The chance that a newly spawned UAP is shielded against all but one flavour of bits.
+    pub shielded_uap_chance: f64,
+    pub projectile_damage: f32,
+    /// The speed of a shell, in pixels per second.
+    pub projectile_speed: f32,
+    /// How fast the launcher turns, in radians per second.
+    pub launcher_rotation_speed: f32,
+    /// How far the launcher turns either way from upright, in radians.
+    pub launcher_max_angle: f32,
+    /// How many launchers stand on the launchpad.
+    pub launchers: usize,
+    /// Seconds a launcher takes to reload after firing.
+    pub launcher_reload_secs: f32,
+    /// How fast the debris of destroyed UAPs falls, in pixels per second squared.
+    pub debris_gravity: f32,
+    /// How long a campaign level goes on before the mothership arrives to end it, in seconds,
+    /// or 0 for levels without one.
+    pub boss_secs: f32,
+    /// The health of the mothership's core. Its other parts have a share of this.
+    pub boss_health: f32,
+}
+
+impl Default for GameRules {
+    /// The Normal preset, used until the rules file has loaded.
+    fn default() -> Self {
+        Self {
+            max_uaps: 10,
+            uap_spawn_secs: 2.0,
+            uap_speed: 200.0,
+            uap_health: 100.0,
+            tough_uap_chance: 0.2,
+            tough_uap_speed: 140.0,
+            tough_uap_health: 200.0,
+            shielded_uap_chance: 0.1,
+            projectile_damage: 20.0,
+            projectile_speed: 500.0,
+            launcher_rotation_speed: 2.0,
+            launcher_max_angle: 1.2,
+            launchers: 2,
+            launcher_reload_secs: 0.4,
+            debris_gravity: 900.0,
+            boss_secs: 75.0,
+            boss_health: 400.0,
+        }
+    }
+}
+
+/// A twist applied on top of the selected preset.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub enum RulesModifier {
+    #[default]
+    None,
+    /// Debris drifts down slowly, so it has more time to hit other UAPs.
+    LowGravity,
+    DoubleSpeedUaps,
+    /// Most UAPs are tough ones.
+    ToughSwarm,
+    /// UAPs arrive twice as often, and more of them at once.
+    RapidSpawns,
+}
+
+impl RulesModifier {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::LowGravity => "Low gravity",
+            Self::DoubleSpeedUaps => "Double-speed UAPs",
+            Self::ToughSwarm => "Tough swarm",
+            Self::RapidSpawns => "Rapid spawns",
+        }
+    }
+
+    pub fn apply(self, rules: &mut GameRules) {
+        match self {
+            Self::None => {}
+            Self::LowGravity => rules.debris_gravity /= 4.0,
+            Self::DoubleSpeedUaps => {
+                rules.uap_speed *= 2.0;
+                rules.tough_uap_speed *= 2.0;
+            }
+            Self::ToughSwarm => rules.tough_uap_chance = rules.tough_uap_chance.max(0.75),
+            Self::RapidSpawns => {
+                rules.uap_spawn_secs /= 2.0;
+                rules.max_uaps += rules.max_uaps / 2;
+            }
+        }
+    }
+}
+
+/// The rules file.
+#[derive(Resource, Debug)]
+struct GameRulesHandle(Handle<GameRulesAsset>);
+
+/// Every preset in the rules file.
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct GameRulesAsset {
+    presets: HashMap<Difficulty, GameRules>,
+}
+
+impl GameRulesAsset {
+    pub fn preset(&self, difficulty: Difficulty) -> Option<&GameRules> {
+        self.presets.get(&difficulty)
+    }
+}
+
+/// Copy the selected preset into [`GameRules`] when the difficulty, the modifier or the rules
+/// file changes.
+fn apply_game_rules(
+    mut asset_events: EventReader<AssetEvent<GameRulesAsset>>,
+    handle: Res<GameRulesHandle>,
+    rules_assets: Res<Assets<GameRulesAsset>>,
+    difficulty: Res<Difficulty>,
+    modifier: Res<RulesModifier>,
+    mut rules: ResMut<GameRules>,
+) {
+    let rules_changed = asset_events
+        .read()
+        .any(|event| event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0));
+    if !rules_changed && !difficulty.is_changed() && !modifier.is_changed() {
+        return;
+    }
+    let Some(rules_asset) = rules_assets.get(&handle.0) else {
+        return;
+    };
+    match rules_asset.preset(*difficulty) {
+        Some(preset) => {
+            let mut preset = preset.clone();
+            modifier.apply(&mut preset);
+            rules.set_if_neq(preset);
+        }
+        None => warn!(
+            "{RULES_PATH} has no {} preset, keeping the current rules",
+            difficulty.name()
+        ),
+    }
+}
+
+#[derive(Default)]
+struct GameRulesLoader;
+
+impl AssetLoader for GameRulesLoader {
+    type Asset = GameRulesAsset;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<GameRulesAsset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rules.ron"]
+    }
+}
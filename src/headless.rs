@@ -0,0 +1,65 @@
+//! Running [`GamePlugin`] without a window, rendering or audio, advancing [`Time`] by a fixed
+//! step on every update. The tests and the balancing simulation play the game this way.
+
+use std::time::{Duration, Instant};
+
+use bevy::{
+    audio::AudioLoader,
+    image::{CompressedImageFormats, ImageLoader},
+    input::InputPlugin,
+    prelude::*,
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+};
+use bevy_enoki::prelude::*;
+
+use crate::{GamePlugin, asset_tracking::ResourceHandles, game_rules::GameRulesAsset};
+
+/// The time that passes with each update.
+pub const STEP: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// An app running [`GamePlugin`], with its assets still loading.
+pub fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        InputPlugin,
+    ));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(STEP));
+
+    // The asset types and resources the game uses, which the rendering and audio plugins would
+    // add.
+    app.init_asset::<Image>()
+        .register_asset_loader(ImageLoader::new(CompressedImageFormats::NONE));
+    app.init_asset::<TextureAtlasLayout>();
+    app.init_asset::<Mesh>();
+    app.init_asset::<AudioSource>()
+        .init_asset_loader::<AudioLoader>();
+    app.init_resource::<GlobalVolume>();
+    app.init_asset::<Particle2dEffect>()
+        .init_asset_loader::<ParticleEffectLoader>();
+    app.init_asset::<SpriteParticle2dMaterial>();
+
+    app.add_plugins(GamePlugin);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// Keep updating until the game's assets and rules have loaded. Returns false if they haven't
+/// within `timeout`.
+pub fn load_assets(app: &mut App, timeout: Duration) -> bool {
+    let started = Instant::now();
+    while !app.world().resource::<ResourceHandles>().is_all_done()
+        || app.world().resource::<Assets<GameRulesAsset>>().is_empty()
+    {
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    true
+}
@@ -4,24 +4,46 @@
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
 mod app;
+mod asset_manifest;
 mod asset_tracking;
 mod audio;
+mod cli;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod game_rules;
+#[cfg(any(test, feature = "simulation"))]
+mod headless;
 mod menus;
+mod music;
+mod persistence;
+mod rng;
 mod screens;
+mod sprite_atlas;
+#[cfg(test)]
+mod tests;
 mod theme;
 
 use bevy::{
     asset::AssetMetaCheck,
+    audio::{AudioPlugin, SpatialScale},
+    ecs::schedule::ExecutorKind,
     prelude::*,
     window::{WindowMode, WindowResolution},
 };
+use bevy_enoki::EnokiPlugin;
 
+#[cfg(not(feature = "simulation"))]
 fn main() -> AppExit {
     App::new().add_plugins(AppPlugin).run()
 }
 
+/// Balancing builds play rounds headlessly instead of opening the game.
+#[cfg(feature = "simulation")]
+fn main() -> AppExit {
+    app::simulation::run()
+}
+
+#[cfg_attr(feature = "simulation", allow(dead_code))]
 pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
@@ -46,17 +68,36 @@ impl Plugin for AppPlugin {
                     }
                     .into(),
                     ..default()
+                })
+                .set(AudioPlugin {
+                    default_spatial_scale: SpatialScale::new_2d(audio::AUDIO_SCALE),
+                    ..default()
                 }),
         );
+        app.add_plugins(EnokiPlugin);
 
-        // Add other plugins.
+        app.add_plugins(GamePlugin);
+    }
+}
+
+/// The game itself, without the engine plugins it runs on, so that tests can run it without a
+/// window, rendering or audio.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
         app.add_plugins((
+            sprite_atlas::plugin,
+            asset_manifest::plugin,
             asset_tracking::plugin,
             audio::plugin,
+            game_rules::plugin,
             app::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
+            music::plugin,
+            rng::plugin,
             screens::plugin,
             theme::plugin,
         ));
@@ -71,17 +112,36 @@ impl Plugin for AppPlugin {
             )
                 .chain(),
         );
+        app.configure_sets(
+            FixedUpdate,
+            (
+                AppSystems::TickTimers,
+                AppSystems::RecordInput,
+                AppSystems::Update,
+            )
+                .chain(),
+        );
+        // Run fixed steps on one thread, so that systems without an explicit order always run in
+        // the same order and replays stay in sync.
+        app.edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        });
 
         // Set up the `Pause` state.
         app.init_state::<Pause>();
         app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+        app.configure_sets(FixedUpdate, PausableSystems.run_if(in_state(Pause(false))));
+        // Stop the game clock too, which freezes `FixedUpdate` and anything outside the game's
+        // own systems that runs on `Time`, like particles.
+        app.add_systems(OnEnter(Pause(true)), pause_game_time);
+        app.add_systems(OnExit(Pause(true)), resume_game_time);
 
         // Spawn the main camera.
         app.add_systems(Startup, spawn_camera);
     }
 }
 
-/// High-level groupings of systems for the app in the `Update` schedule.
+/// High-level groupings of systems for the app in the `Update` and `FixedUpdate` schedules.
 /// When adding a new variant, make sure to order it in the `configure_sets`
 /// call above.
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
@@ -99,6 +159,14 @@ enum AppSystems {
 #[states(scoped_entities)]
 struct Pause(pub bool);
 
+fn pause_game_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_game_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
 /// A system set for systems that shouldn't run while the game is paused.
 #[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct PausableSystems;
@@ -111,5 +179,7 @@ fn spawn_camera(mut commands: Commands) {
             clear_color: ClearColorConfig::Custom(Color::BLACK),
             ..default()
         },
+        // Hear positional sounds from the middle of the screen.
+        SpatialListener::new(audio::LISTENER_EAR_GAP),
     ));
 }
@@ -2,7 +2,10 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    app::daily_challenge::StartDailyChallenge, asset_tracking::ResourceHandles, menus::Menu,
+    rng::Date, screens::Screen, theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -16,6 +19,7 @@ fn spawn_main_menu(mut commands: Commands) {
         #[cfg(not(target_family = "wasm"))]
         children![
             widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Daily Challenge", start_daily_challenge),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
@@ -23,6 +27,7 @@ fn spawn_main_menu(mut commands: Commands) {
         #[cfg(target_family = "wasm")]
         children![
             widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Daily Challenge", start_daily_challenge),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
         ],
@@ -41,6 +46,16 @@ fn enter_loading_or_gameplay_screen(
     }
 }
 
+fn start_daily_challenge(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    resource_handles: Res<ResourceHandles>,
+    next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.queue(StartDailyChallenge(Date::today()));
+    enter_loading_or_gameplay_screen(trigger, resource_handles, next_screen);
+}
+
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
@@ -4,7 +4,7 @@
 
 use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{audio::AudioOutput, menus::Menu, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -18,6 +18,12 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
     );
+
+    app.register_type::<AudioOutputLabel>();
+    app.add_systems(
+        Update,
+        update_audio_output_label.run_if(in_state(Menu::Settings)),
+    );
 }
 
 fn spawn_settings_menu(mut commands: Commands) {
@@ -52,6 +58,14 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Audio Output"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            audio_output_widget(),
         ],
     )
 }
@@ -104,6 +118,51 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn audio_output_widget() -> impl Bundle {
+    (
+        Name::new("Audio Output Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_audio_output),
+            (
+                Name::new("Current Audio Output"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), AudioOutputLabel)],
+            ),
+            widget::button_small(">", toggle_audio_output),
+        ],
+    )
+}
+
+fn toggle_audio_output(_: Trigger<Pointer<Click>>, mut audio_output: ResMut<AudioOutput>) {
+    *audio_output = match *audio_output {
+        AudioOutput::Stereo => AudioOutput::Mono,
+        AudioOutput::Mono => AudioOutput::Stereo,
+    };
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AudioOutputLabel;
+
+fn update_audio_output_label(
+    audio_output: Res<AudioOutput>,
+    mut label: Single<&mut Text, With<AudioOutputLabel>>,
+) {
+    label.0 = match *audio_output {
+        AudioOutput::Stereo => "Stereo",
+        AudioOutput::Mono => "Mono",
+    }
+    .to_string();
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -0,0 +1,233 @@
+//! A music director that picks the soundtrack based on what's happening in the game
+//! and crossfades between tracks instead of cutting them off.
+//!
+//! Gameplay code only describes the current [`MusicMood`] through the [`MusicDirector`]
+//! resource. The director owns the [`Music`](crate::audio::Music) entities, so they outlive screen transitions
+//! and can fade out smoothly.
+
+use bevy::{
+    asset::{UntypedAssetId, VisitAssetDependencies},
+    audio::Volume,
+    prelude::*,
+};
+
+use crate::{
+    AppSystems, Pause,
+    asset_manifest::{AssetManifest, FromAssetManifest, MissingAssetKey},
+    asset_tracking::LoadResource,
+    audio::music,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MusicAssets>();
+    app.load_resource::<MusicAssets>();
+
+    app.register_type::<MusicDirector>();
+    app.init_resource::<MusicDirector>();
+
+    app.register_type::<MusicLayer>();
+    app.add_systems(
+        Update,
+        (
+            duck_music.run_if(state_changed::<Pause>),
+            direct_music.run_if(
+                resource_exists::<MusicAssets>
+                    .and(resource_changed::<MusicDirector>.or(resource_added::<MusicAssets>)),
+            ),
+            fade_music_layers,
+        )
+            .chain()
+            .in_set(AppSystems::Update),
+    );
+}
+
+/// How long it takes to fade a track fully in or out, in seconds.
+const FADE_DURATION_SECS: f32 = 1.5;
+
+/// The volume of the music while the game is paused, relative to its regular volume.
+const DUCKED_VOLUME: f32 = 0.3;
+
+/// How long it takes to adjust the speed of a playing track to a new mood, in seconds.
+const SPEED_CHANGE_DURATION_SECS: f32 = 3.0;
+
+/// The overall feel of the music that should be playing.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicMood {
+    /// Tinkering in the workshop.
+    Calm,
+    /// Fending off UAPs on the launchpad.
+    Action,
+    /// The launchpad is swarming with UAPs.
+    Boss,
+    /// Looking back on a finished round.
+    Results,
+    /// Rolling the credits.
+    Credits,
+}
+
+impl MusicMood {
+    /// The track, playback speed and volume used for this mood. Moods sharing a track blend
+    /// into each other by adjusting the speed and volume of the playing track.
+    fn settings(self, assets: &MusicAssets) -> (Handle<AudioSource>, f32, f32) {
+        match self {
+            Self::Calm => (assets.calm.clone(), 1.0, 1.0),
+            Self::Action => (assets.upbeat.clone(), 1.0, 1.0),
+            Self::Boss => (assets.upbeat.clone(), 1.12, 1.2),
+            Self::Results => (assets.calm.clone(), 0.9, 0.7),
+            Self::Credits => (assets.upbeat.clone(), 1.0, 1.0),
+        }
+    }
+}
+
+/// The mood the music should currently be in. Set this to change the soundtrack; the
+/// director takes care of fading between tracks.
+#[derive(Resource, Reflect, Debug, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct MusicDirector {
+    /// The current mood, or `None` for silence.
+    pub mood: Option<MusicMood>,
+    /// Whether to play the music quieter, e.g. while the game is paused.
+    pub ducked: bool,
+}
+
+/// A system that changes the music to the given mood, e.g. when entering a screen.
+pub fn change_mood(mood: Option<MusicMood>) -> impl Fn(ResMut<MusicDirector>) {
+    move |mut director| {
+        if director.mood != mood {
+            director.mood = mood;
+        }
+    }
+}
+
+/// A single playing track managed by the director.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MusicLayer {
+    track: Handle<AudioSource>,
+    volume: f32,
+    target_volume: f32,
+    speed: f32,
+    target_speed: f32,
+}
+
+/// Point the playing tracks at the new mood: fade in its track (reusing it if it's already
+/// playing) and fade out everything else.
+fn direct_music(
+    mut commands: Commands,
+    director: Res<MusicDirector>,
+    music_assets: Res<MusicAssets>,
+    mut layer_query: Query<&mut MusicLayer>,
+) {
+    let target = director.mood.map(|mood| {
+        let (track, speed, volume) = mood.settings(&music_assets);
+        let volume = if director.ducked {
+            volume * DUCKED_VOLUME
+        } else {
+            volume
+        };
+        (track, speed, volume)
+    });
+
+    let mut found = false;
+    for mut layer in &mut layer_query {
+        match &target {
+            Some((track, speed, volume)) if *track == layer.track => {
+                layer.target_volume = *volume;
+                layer.target_speed = *speed;
+                found = true;
+            }
+            _ => layer.target_volume = 0.0,
+        }
+    }
+
+    if let Some((track, speed, volume)) = target.filter(|_| !found) {
+        commands
+            .spawn((
+                Name::new("Music"),
+                music(track.clone()),
+                MusicLayer {
+                    track,
+                    volume: 0.0,
+                    target_volume: volume,
+                    speed,
+                    target_speed: speed,
+                },
+            ))
+            .insert(
+                PlaybackSettings::LOOP
+                    .with_volume(Volume::SILENT)
+                    .with_speed(speed),
+            );
+    }
+}
+
+fn duck_music(pause: Res<State<Pause>>, mut director: ResMut<MusicDirector>) {
+    let ducked = pause.get().0;
+    if director.ducked != ducked {
+        director.ducked = ducked;
+    }
+}
+
+/// Move every track's volume and speed towards its target, despawning tracks once they've
+/// faded out. This runs on real time, so the music keeps fading while the game is paused.
+fn fade_music_layers(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    global_volume: Res<GlobalVolume>,
+    mut layer_query: Query<(
+        Entity,
+        &mut MusicLayer,
+        &mut PlaybackSettings,
+        Option<&mut AudioSink>,
+    )>,
+) {
+    let volume_step = time.delta_secs() / FADE_DURATION_SECS;
+    let speed_step = time.delta_secs() / SPEED_CHANGE_DURATION_SECS;
+
+    for (entity, mut layer, mut playback, sink) in &mut layer_query {
+        if layer.volume == layer.target_volume && layer.speed == layer.target_speed {
+            continue;
+        }
+
+        layer.volume = move_towards(layer.volume, layer.target_volume, volume_step);
+        layer.speed = move_towards(layer.speed, layer.target_speed, speed_step);
+        if layer.volume == 0.0 && layer.target_volume == 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Keep the playback settings in sync so `apply_global_volume` uses the faded volume.
+        playback.volume = Volume::Linear(layer.volume);
+        if let Some(mut sink) = sink {
+            sink.set_volume(global_volume.volume * playback.volume);
+            sink.set_speed(layer.speed);
+        }
+    }
+}
+
+fn move_towards(current: f32, target: f32, max_step: f32) -> f32 {
+    current + (target - current).clamp(-max_step, max_step)
+}
+
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct MusicAssets {
+    calm: Handle<AudioSource>,
+    upbeat: Handle<AudioSource>,
+}
+
+impl FromAssetManifest for MusicAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            calm: manifest.audio("music_calm")?,
+            upbeat: manifest.audio("music_upbeat")?,
+        })
+    }
+}
+
+impl VisitAssetDependencies for MusicAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.calm.visit_dependencies(visit);
+        self.upbeat.visit_dependencies(visit);
+    }
+}
@@ -0,0 +1,66 @@
+//! Saving and loading the player's progress as RON files in the `save` folder next to the
+//! assets.
+//!
+//! Web builds have no file system, so nothing is saved there and every load comes up empty. The
+//! same goes for tests and the balancing simulation, which shouldn't touch the player's
+//! progress.
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Load the save file called `name`, or `None` if there isn't a readable one.
+#[cfg(not(any(test, feature = "simulation", target_family = "wasm")))]
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let path = native::save_path(name);
+    let bytes = std::fs::read(&path).ok()?;
+    match ron::de::from_bytes(&bytes) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            bevy::log::warn!(
+                "Ignoring the unreadable save file {}: {error}",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+#[cfg(any(test, feature = "simulation", target_family = "wasm"))]
+pub fn load<T: DeserializeOwned>(_name: &str) -> Option<T> {
+    None
+}
+
+/// Write `value` to the save file called `name`, logging any failure.
+#[cfg(not(any(test, feature = "simulation", target_family = "wasm")))]
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    let path = native::save_path(name);
+    if let Err(error) = native::write(&path, value) {
+        bevy::log::error!("Couldn't save {}: {error}", path.display());
+    }
+}
+
+#[cfg(any(test, feature = "simulation", target_family = "wasm"))]
+pub fn save<T: Serialize>(_name: &str, _value: &T) {}
+
+#[cfg(not(any(test, feature = "simulation", target_family = "wasm")))]
+mod native {
+    use std::{error::Error, path::PathBuf};
+
+    use bevy::asset::io::file::FileAssetReader;
+    use serde::Serialize;
+
+    /// Where a save file called `name` lives.
+    pub(super) fn save_path(name: &str) -> PathBuf {
+        FileAssetReader::get_base_path().join("save").join(name)
+    }
+
+    pub(super) fn write<T: Serialize>(path: &PathBuf, value: &T) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(
+            path,
+            ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?,
+        )?;
+        Ok(())
+    }
+}
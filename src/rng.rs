@@ -0,0 +1,219 @@
+//! Seeded random number generators for gameplay, so that a round plays out the same when it's
+//! played again with the same seed and inputs.
+//!
+//! [`GameRng`] is split into [`RngStream`]s, so that drawing more numbers for one purpose
+//! doesn't change what another gets. The gameplay streams follow the round's seed, while the
+//! cosmetic and audio streams are free to differ between runs.
+//!
+//! Every launchpad round is seeded from the [`RoundSeed`], which is random unless it's set with
+//! `--seed <number>` or `--daily [YYYY-MM-DD]` on the command line.
+
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::{cli, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GameRng>();
+    app.register_type::<RoundSeed>();
+    app.insert_resource(RoundSeed::from_args());
+
+    app.add_systems(OnEnter(Screen::Launchpad), seed_round);
+}
+
+/// What a [`GameRng`] stream is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    /// Where UAPs appear, which kind they are and which way they fly.
+    Spawning,
+    /// How destroyed UAPs break apart. Falling debris can hit other UAPs, so this affects
+    /// gameplay too.
+    Debris,
+    /// Visual variety that doesn't affect gameplay.
+    Cosmetics,
+    /// Which variant of a sound plays, and how it's pitched.
+    Audio,
+}
+
+impl RngStream {
+    const ALL: [Self; 4] = [Self::Spawning, Self::Debris, Self::Cosmetics, Self::Audio];
+
+    /// Whether the stream affects gameplay, and so follows the seed.
+    fn is_gameplay(self) -> bool {
+        matches!(self, Self::Spawning | Self::Debris)
+    }
+}
+
+/// The random number generators for everything that happens in a round. Use a stream of it
+/// instead of `rand::thread_rng` wherever randomness is needed.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: [StdRng; RngStream::ALL.len()],
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::seeded(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: RngStream::ALL.map(|stream| {
+                if stream.is_gameplay() {
+                    StdRng::seed_from_u64(mix(seed, stream as u64))
+                } else {
+                    StdRng::from_entropy()
+                }
+            }),
+        }
+    }
+
+    /// The seed the gameplay streams were created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+/// Derive the seed of a stream, so that streams of neighbouring seeds aren't alike (SplitMix64).
+fn mix(seed: u64, stream: u64) -> u64 {
+    let mut z = seed
+        .wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Where the seed of the next launchpad round comes from.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub enum RoundSeed {
+    /// A new seed every round.
+    #[default]
+    Random,
+    Fixed(u64),
+    /// The same seed for everyone playing on the same day.
+    Daily(Date),
+}
+
+impl RoundSeed {
+    fn from_args() -> Self {
+        if let Some(seed) = cli::value("--seed") {
+            match seed.parse() {
+                Ok(seed) => return Self::Fixed(seed),
+                Err(_) => warn!("Invalid value for --seed: {seed}"),
+            }
+        }
+        if cli::is_set("--daily") {
+            // The date is optional, so the next argument may well be another option.
+            let date = cli::value("--daily").as_deref().and_then(Date::parse);
+            return Self::Daily(date.unwrap_or_else(Date::today));
+        }
+        Self::Random
+    }
+
+    pub fn seed(self) -> u64 {
+        match self {
+            Self::Random => rand::random(),
+            Self::Fixed(seed) => seed,
+            Self::Daily(date) => date.seed(),
+        }
+    }
+}
+
+/// A calendar date, in UTC.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        Self::from_days_since_epoch((secs / 86_400) as i64)
+    }
+
+    /// Parse a date written as `YYYY-MM-DD`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '-');
+        let date = Self {
+            year: parts.next()?.parse().ok()?,
+            month: parts.next()?.parse().ok()?,
+            day: parts.next()?.parse().ok()?,
+        };
+        ((1..=12).contains(&date.month) && (1..=date.days_in_month()).contains(&date.day))
+            .then_some(date)
+    }
+
+    /// How many days the month of the date has. Expects a month between 1 and 12.
+    fn days_in_month(self) -> u32 {
+        match self.month {
+            2 if self.is_leap_year() => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    fn is_leap_year(self) -> bool {
+        self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0)
+    }
+
+    /// The seed of the day's challenge.
+    pub fn seed(self) -> u64 {
+        mix(
+            ((self.year as u64) << 16) | ((self.month as u64) << 8) | self.day as u64,
+            u64::MAX,
+        )
+    }
+
+    /// Convert days since 1970-01-01 to a date in the proleptic Gregorian calendar.
+    /// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+    fn from_days_since_epoch(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Reseed the gameplay streams at the start of every round.
+pub(super) fn seed_round(round_seed: Res<RoundSeed>, mut rng: ResMut<GameRng>) {
+    *rng = GameRng::seeded(round_seed.seed());
+}
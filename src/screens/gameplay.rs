@@ -2,10 +2,19 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{Pause, app::level::spawn_level, menus::Menu, screens::Screen};
+use crate::{
+    Pause,
+    app::level::spawn_level,
+    menus::Menu,
+    music::{MusicMood, change_mood},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (spawn_level, change_mood(Some(MusicMood::Calm))),
+    );
 
     // Toggle pause on key press.
     app.add_systems(
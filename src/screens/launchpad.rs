@@ -4,14 +4,17 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*}
 
 use crate::{
     Pause,
-    app::launcher::{LauncherAssets, LauncherCrankAssets, launcher, launcher_crank},
     menus::Menu,
+    music::{MusicMood, change_mood},
     screens::Screen,
     theme::widget,
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Launchpad), spawn_launchpad);
+    app.add_systems(
+        OnEnter(Screen::Launchpad),
+        (spawn_launchpad, change_mood(Some(MusicMood::Action))),
+    );
 
     // Toggle pause on key press.
     app.add_systems(
@@ -36,22 +39,7 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn spawn_launchpad(
-    mut commands: Commands,
-    launcher_assets: Res<LauncherAssets>,
-    launcher_crank_assets: Res<LauncherCrankAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-) {
-    commands.spawn((
-        Transform::default(),
-        Visibility::default(),
-        StateScoped(Screen::Launchpad),
-        children![
-            launcher(&launcher_assets, &mut texture_atlas_layouts),
-            launcher_crank(&launcher_crank_assets, &mut texture_atlas_layouts),
-        ],
-    ));
-
+fn spawn_launchpad(mut commands: Commands) {
     commands.spawn((
         widget::button("Back to workshop", workshop_return),
         StateScoped(Screen::Launchpad),
@@ -1,16 +1,23 @@
 //! A loading screen during which game assets are loaded if necessary.
 //! This reduces stuttering, especially for audio on Wasm.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::Val::*};
 
 use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
 
+    app.register_type::<LoadingProgressFill>();
+    app.register_type::<LoadingLabel>();
     app.add_systems(
         Update,
-        enter_gameplay_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
+        (
+            update_loading_progress,
+            enter_gameplay_screen.run_if(all_assets_loaded),
+            enter_loading_failed_screen.run_if(any_assets_failed),
+        )
+            .run_if(in_state(Screen::Loading)),
     );
 }
 
@@ -18,14 +25,45 @@ fn spawn_loading_screen(mut commands: Commands) {
     commands.spawn((
         widget::ui_root("Loading Screen"),
         StateScoped(Screen::Loading),
-        children![widget::label("Loading...")],
+        children![
+            (widget::label("Loading..."), LoadingLabel),
+            widget::progress_bar(LoadingProgressFill),
+        ],
     ));
 }
 
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LoadingProgressFill;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LoadingLabel;
+
+fn update_loading_progress(
+    resource_handles: Res<ResourceHandles>,
+    mut fill: Single<&mut Node, With<LoadingProgressFill>>,
+    mut label: Single<&mut Text, With<LoadingLabel>>,
+) {
+    fill.width = Percent(100.0 * resource_handles.progress());
+    label.0 = match resource_handles.currently_loading() {
+        Some(name) => format!("Loading {name}..."),
+        None => "Loading...".to_string(),
+    };
+}
+
 fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Gameplay);
 }
 
+fn enter_loading_failed_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::LoadingFailed);
+}
+
 fn all_assets_loaded(resource_handles: Res<ResourceHandles>) -> bool {
     resource_handles.is_all_done()
 }
+
+fn any_assets_failed(resource_handles: Res<ResourceHandles>) -> bool {
+    resource_handles.has_failed()
+}
@@ -0,0 +1,64 @@
+//! An error screen shown when some of the game's assets failed to load, instead of
+//! waiting on the loading screen forever.
+
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+
+use crate::{asset_tracking::ResourceLoadFailed, screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    // Assets load in the background from startup, so failures are recorded whenever they happen.
+    app.init_resource::<LoadFailures>();
+    app.add_systems(Update, record_load_failures);
+
+    app.add_systems(OnEnter(Screen::LoadingFailed), spawn_loading_failed_screen);
+}
+
+/// The resource names and asset files that failed to load without a placeholder to fall back on.
+#[derive(Resource, Default)]
+struct LoadFailures(Vec<(String, String)>);
+
+fn record_load_failures(
+    mut failed_events: EventReader<ResourceLoadFailed>,
+    mut failures: ResMut<LoadFailures>,
+) {
+    for event in failed_events.read().filter(|event| !event.substituted) {
+        failures.0.extend(
+            event
+                .paths
+                .iter()
+                .map(|path| (event.resource.clone(), path.clone())),
+        );
+    }
+}
+
+fn spawn_loading_failed_screen(mut commands: Commands, failures: Res<LoadFailures>) {
+    let labels = failures
+        .0
+        .iter()
+        .map(|(resource, path)| {
+            widget::label(format!("Could not load the {resource} asset {path}"))
+        })
+        .collect::<Vec<_>>();
+
+    commands.spawn((
+        widget::ui_root("Loading Failed Screen"),
+        StateScoped(Screen::LoadingFailed),
+        children![
+            widget::header("Something went wrong"),
+            (
+                Name::new("Failed Assets"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                Children::spawn(SpawnIter(labels.into_iter())),
+            ),
+            widget::button("Quit to title", quit_to_title),
+        ],
+    ));
+}
+
+fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -3,6 +3,8 @@
 mod gameplay;
 mod launchpad;
 mod loading;
+mod loading_failed;
+mod results;
 mod splash;
 mod title;
 mod workshop;
@@ -15,10 +17,12 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         gameplay::plugin,
         loading::plugin,
+        loading_failed::plugin,
         splash::plugin,
         title::plugin,
         workshop::plugin,
         launchpad::plugin,
+        results::plugin,
     ));
 }
 
@@ -30,7 +34,9 @@ pub enum Screen {
     Splash,
     Title,
     Loading,
+    LoadingFailed,
     Gameplay,
     Workshop,
     Launchpad,
+    Results,
 }
@@ -0,0 +1,99 @@
+//! The screen after a round has come to an end, with its score and the high scores.
+
+use std::cmp::Ordering;
+
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
+
+use crate::{
+    app::{coop::PlayerId, game_mode::RoundResults, high_scores::HighScores},
+    music::{MusicMood, change_mood},
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Results),
+        (spawn_results_screen, change_mood(Some(MusicMood::Results))),
+    );
+}
+
+/// How many high scores to list.
+const LISTED_HIGH_SCORES: usize = 5;
+
+fn spawn_results_screen(
+    mut commands: Commands,
+    results: Option<Res<RoundResults>>,
+    high_scores: Res<HighScores>,
+) {
+    let Some(results) = results else {
+        return;
+    };
+    let verdict = match results.place {
+        Some(0) => "New high score!".to_string(),
+        Some(place) => format!("Number {} on the high scores", place + 1),
+        None => String::new(),
+    };
+    let listed = high_scores
+        .of(results.mode)
+        .iter()
+        .take(LISTED_HIGH_SCORES)
+        .enumerate()
+        .map(|(place, score)| widget::label(format!("{}. {score}", place + 1)))
+        .collect::<Vec<_>>();
+    // Players who played against each other see who won.
+    let standings = results
+        .player_scores
+        .map(|scores| {
+            let winner = match scores[0].cmp(&scores[1]) {
+                Ordering::Greater => format!("{} wins!", PlayerId::One.name()),
+                Ordering::Less => format!("{} wins!", PlayerId::Two.name()),
+                Ordering::Equal => "It's a draw!".to_string(),
+            };
+            let scores = PlayerId::ALL
+                .map(|player| format!("{}: {}", player.name(), scores[player.index()]))
+                .join("   ");
+            vec![widget::label(scores), widget::label(winner)]
+        })
+        .unwrap_or_default();
+
+    commands.spawn((
+        widget::ui_root("Results Screen"),
+        StateScoped(Screen::Results),
+        children![
+            widget::header(results.reason),
+            widget::label(format!("{} score: {}", results.mode.name(), results.score)),
+            widget::label(verdict),
+            (
+                Name::new("Standings"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(5.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(standings.into_iter())),
+            ),
+            (
+                Name::new("High Scores"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(5.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(listed.into_iter())),
+            ),
+            widget::button("Play again", play_again),
+            widget::button("Back to workshop", return_to_workshop),
+        ],
+    ));
+}
+
+fn play_again(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Launchpad);
+}
+
+fn return_to_workshop(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Workshop);
+}
@@ -2,10 +2,10 @@
 
 use bevy::prelude::*;
 
-use crate::{menus::Menu, screens::Screen};
+use crate::{menus::Menu, music::change_mood, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Title), open_main_menu);
+    app.add_systems(OnEnter(Screen::Title), (open_main_menu, change_mood(None)));
     app.add_systems(OnExit(Screen::Title), close_menu);
 }
 
@@ -1,20 +1,64 @@
 //! Screen for creating firework bits.
 
 use bevy::{
+    ecs::spawn::SpawnIter,
     image::{ImageLoaderSettings, ImageSampler},
     input::common_conditions::input_just_pressed,
     prelude::*,
     ui::Val::*,
 };
 
-use crate::{Pause, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    Pause,
+    app::{
+        bits::{Bit, DamageTag, MAX_STAGES, SHELL_GRID_SIZE, ShellDesign, Stage},
+        coop::CoopMode,
+        daily_challenge::{DailyChallenge, DailyScores},
+        game_mode::GameMode,
+        upgrades::{Progress, Upgrade},
+    },
+    game_rules::Difficulty,
+    menus::Menu,
+    music::{MusicMood, change_mood},
+    screens::Screen,
+    theme::widget,
+};
 
 const WORKSHOP_TILE_WIDTH: f32 = 64.;
 const WORKSHOP_COLUMNS: f32 = 8.;
 const WORKSHOP_ROWS: f32 = 8.;
 
+/// The size of a bit in the palette.
+const PALETTE_BIT_WIDTH: f32 = 48.;
+
+/// The tint of each stage's rows of the shell grid and of its controls.
+const STAGE_TINTS: [Color; MAX_STAGES] = [
+    Color::srgba(0.3, 0.6, 1.0, 0.2),
+    Color::srgba(0.3, 1.0, 0.5, 0.2),
+    Color::srgba(1.0, 0.6, 0.3, 0.2),
+];
+
+/// How many past days of daily challenge scores to show.
+const RECENT_DAILY_SCORES: usize = 5;
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Workshop), spawn_workshop);
+    app.add_systems(
+        OnEnter(Screen::Workshop),
+        (
+            (
+                spawn_workshop,
+                (
+                    update_shell_grid,
+                    update_mix_labels,
+                    update_palette,
+                    update_stage_panel,
+                    update_upgrade_labels,
+                ),
+            )
+                .chain(),
+            change_mood(Some(MusicMood::Calm)),
+        ),
+    );
 
     // Toggle pause on key press.
     app.add_systems(
@@ -37,6 +81,37 @@ pub(super) fn plugin(app: &mut App) {
         OnEnter(Menu::None),
         unpause.run_if(in_state(Screen::Workshop)),
     );
+
+    app.register_type::<DifficultyLabel>();
+    app.register_type::<GameModeLabel>();
+    app.register_type::<CoopModeLabel>();
+    app.add_systems(
+        Update,
+        (
+            update_difficulty_label,
+            update_game_mode_label,
+            update_coop_mode_label,
+        )
+            .run_if(in_state(Screen::Workshop)),
+    );
+
+    app.register_type::<StageControls>();
+    app.register_type::<StageValueLabel>();
+    app.register_type::<CreditsLabel>();
+    app.register_type::<UpgradeLabel>();
+    app.register_type::<SelectedBit>();
+    app.init_resource::<SelectedBit>();
+    app.add_systems(
+        Update,
+        (
+            update_shell_grid.run_if(resource_changed::<ShellDesign>),
+            update_mix_labels.run_if(resource_changed::<ShellDesign>),
+            update_stage_panel.run_if(resource_changed::<ShellDesign>),
+            update_palette.run_if(resource_changed::<SelectedBit>),
+            update_upgrade_labels.run_if(resource_changed::<Progress>),
+        )
+            .run_if(in_state(Screen::Workshop)),
+    );
 }
 
 fn create_menu_panel(asset_server: &Res<AssetServer>) -> impl Bundle {
@@ -70,6 +145,460 @@ fn create_menu_panel(asset_server: &Res<AssetServer>) -> impl Bundle {
     )
 }
 
+/// The bit that clicking a cell of the shell grid places.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+struct SelectedBit(Bit);
+
+impl Default for SelectedBit {
+    fn default() -> Self {
+        Self(Bit::ALL[0])
+    }
+}
+
+#[derive(Component)]
+struct PaletteBit(Bit);
+
+#[derive(Component)]
+struct ShellCell(usize);
+
+#[derive(Component)]
+struct MixLabel(DamageTag);
+
+/// A column of the bits that can be used, to pick the one to place.
+fn spawn_bit_palette(panel: &mut ChildSpawnerCommands, asset_server: &AssetServer, bits: &[Bit]) {
+    for &bit in bits {
+        panel
+            .spawn((
+                Name::new("Palette Bit"),
+                PaletteBit(bit),
+                Button,
+                ImageNode::new(asset_server.load(bit.image_path())),
+                Node {
+                    width: Px(PALETTE_BIT_WIDTH),
+                    height: Px(PALETTE_BIT_WIDTH),
+                    margin: UiRect::axes(Auto, Px(12.0)),
+                    ..default()
+                },
+                Outline::new(Px(3.0), Px(0.0), Color::NONE),
+            ))
+            .observe(select_bit);
+    }
+}
+
+fn select_bit(
+    trigger: Trigger<Pointer<Click>>,
+    bit_query: Query<&PaletteBit>,
+    mut selected: ResMut<SelectedBit>,
+) {
+    if let Ok(bit) = bit_query.get(trigger.target()) {
+        selected.0 = bit.0;
+    }
+}
+
+fn update_palette(selected: Res<SelectedBit>, mut bit_query: Query<(&PaletteBit, &mut Outline)>) {
+    for (bit, mut outline) in &mut bit_query {
+        outline.color = if bit.0 == selected.0 {
+            bit.0.tag().color()
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// A cell for every slot of the shell, laid over the workspace tiles.
+fn spawn_shell_grid(panel: &mut ChildSpawnerCommands) {
+    panel
+        .spawn((
+            Name::new("Shell Grid"),
+            Node {
+                display: Display::Grid,
+                width: Percent(100.0),
+                height: Percent(100.0),
+                grid_template_columns: RepeatedGridTrack::px(
+                    SHELL_GRID_SIZE as u16,
+                    WORKSHOP_TILE_WIDTH,
+                ),
+                grid_template_rows: RepeatedGridTrack::px(
+                    SHELL_GRID_SIZE as u16,
+                    WORKSHOP_TILE_WIDTH,
+                ),
+                ..default()
+            },
+        ))
+        .with_children(|grid| {
+            for cell in 0..SHELL_GRID_SIZE * SHELL_GRID_SIZE {
+                grid.spawn((
+                    Name::new("Shell Cell"),
+                    ShellCell(cell),
+                    Button,
+                    BackgroundColor::default(),
+                    Node {
+                        padding: UiRect::all(Px(8.0)),
+                        ..default()
+                    },
+                    children![(
+                        Name::new("Shell Cell Bit"),
+                        ImageNode::default().with_color(Color::NONE),
+                        Node {
+                            width: Percent(100.0),
+                            height: Percent(100.0),
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                    )],
+                ))
+                .observe(toggle_bit);
+            }
+        });
+}
+
+/// Place the selected bit in the clicked cell, or take it out if it's already there.
+fn toggle_bit(
+    trigger: Trigger<Pointer<Click>>,
+    cell_query: Query<&ShellCell>,
+    selected: Res<SelectedBit>,
+    mut design: ResMut<ShellDesign>,
+) {
+    let Ok(cell) = cell_query.get(trigger.target()) else {
+        return;
+    };
+    let bit = (design.get(cell.0) != Some(selected.0)).then_some(selected.0);
+    design.set(cell.0, bit);
+}
+
+fn update_shell_grid(
+    design: Res<ShellDesign>,
+    asset_server: Res<AssetServer>,
+    mut cell_query: Query<(&ShellCell, &Children, &mut BackgroundColor)>,
+    mut image_query: Query<&mut ImageNode>,
+) {
+    for (cell, children, mut background) in &mut cell_query {
+        background.0 = STAGE_TINTS[design.stage_of(cell.0)];
+        let mut images = image_query.iter_many_mut(children);
+        while let Some(mut image) = images.fetch_next() {
+            match design.get(cell.0) {
+                Some(bit) => {
+                    image.image = asset_server.load(bit.image_path());
+                    image.color = Color::WHITE;
+                }
+                None => image.color = Color::NONE,
+            }
+        }
+    }
+}
+
+/// Shows how the shell's damage is split between flavours.
+fn mix_panel() -> impl Bundle {
+    (
+        Name::new("Mix Panel"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(100.0),
+            right: Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(10.0),
+            ..default()
+        },
+        Children::spawn((
+            Spawn(widget::label("Shell flavours")),
+            SpawnIter(
+                DamageTag::ALL
+                    .into_iter()
+                    .map(|tag| (widget::label(""), MixLabel(tag))),
+            ),
+        )),
+    )
+}
+
+fn update_mix_labels(
+    design: Res<ShellDesign>,
+    mut label_query: Query<(&MixLabel, &mut Text, &mut TextColor)>,
+) {
+    let mix = design.damage_mix();
+    for (label, mut text, mut color) in &mut label_query {
+        color.0 = label.0.color();
+        text.0 = format!("{}: {:.0}%", label.0.name(), mix.share(label.0) * 100.0);
+    }
+}
+
+/// A setting of a shell stage that can be stepped up and down in the workshop.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+enum StageSetting {
+    Rows,
+    Count,
+    Spread,
+    Fuse,
+}
+
+impl StageSetting {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Rows => "Rows",
+            Self::Count => "Shells",
+            Self::Spread => "Spread",
+            Self::Fuse => "Fuse",
+        }
+    }
+
+    fn step(self, design: &mut ShellDesign, stage: usize, steps: i32) {
+        if self == Self::Rows {
+            design.resize_stage(stage, steps);
+            return;
+        }
+        let Some(stage) = design.stage_mut(stage) else {
+            return;
+        };
+        match self {
+            Self::Rows => {}
+            Self::Count => stage.step_count(steps),
+            Self::Spread => stage.step_spread(steps),
+            Self::Fuse => stage.step_fuse(steps),
+        }
+    }
+
+    fn value(self, stage: &Stage) -> String {
+        match self {
+            Self::Rows => stage.rows.to_string(),
+            Self::Count => stage.count.to_string(),
+            Self::Spread => format!("{:.0}°", stage.spread_degrees),
+            Self::Fuse if stage.fuse_secs <= 0.0 => "Contact".to_string(),
+            Self::Fuse => format!("{:.2}s", stage.fuse_secs),
+        }
+    }
+}
+
+/// Controls that are only shown while the design has the stage, and the one after it if
+/// `needs_next_stage`.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct StageControls {
+    stage: usize,
+    needs_next_stage: bool,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct StageValueLabel {
+    stage: usize,
+    setting: StageSetting,
+}
+
+/// Adds and removes shell stages and steps their settings.
+fn stage_panel() -> impl Bundle {
+    (
+        Name::new("Stage Panel"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(280.0),
+            right: Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(10.0),
+            ..default()
+        },
+        Children::spawn((
+            Spawn((
+                Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Px(10.0),
+                    ..default()
+                },
+                children![
+                    widget::label("Stages"),
+                    widget::button_small("-", remove_stage),
+                    widget::button_small("+", add_stage),
+                ],
+            )),
+            SpawnIter((0..MAX_STAGES).map(stage_controls)),
+        )),
+    )
+}
+
+fn stage_controls(stage: usize) -> impl Bundle {
+    // The first stage is the shell fired from the launcher, so it doesn't split.
+    let settings: &[StageSetting] = if stage == 0 {
+        &[StageSetting::Rows, StageSetting::Fuse]
+    } else {
+        &[
+            StageSetting::Rows,
+            StageSetting::Count,
+            StageSetting::Spread,
+            StageSetting::Fuse,
+        ]
+    };
+    (
+        Name::new("Stage Controls"),
+        StageControls {
+            stage,
+            needs_next_stage: false,
+        },
+        Node {
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(STAGE_TINTS[stage]),
+        Children::spawn((
+            Spawn(widget::label(format!("Stage {}", stage + 1))),
+            SpawnIter(
+                settings
+                    .iter()
+                    .map(move |&setting| stage_stepper(stage, setting)),
+            ),
+        )),
+    )
+}
+
+fn stage_stepper(stage: usize, setting: StageSetting) -> impl Bundle {
+    (
+        Name::new("Stage Stepper"),
+        StageControls {
+            stage,
+            // Rows are moved across the border with the next stage.
+            needs_next_stage: setting == StageSetting::Rows,
+        },
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Px(6.0),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: Px(90.0),
+                    ..default()
+                },
+                children![widget::label(setting.name())],
+            ),
+            widget::button_small(
+                "<",
+                move |_: Trigger<Pointer<Click>>, mut design: ResMut<ShellDesign>| {
+                    setting.step(&mut design, stage, -1);
+                },
+            ),
+            (
+                Node {
+                    width: Px(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), StageValueLabel { stage, setting })],
+            ),
+            widget::button_small(
+                ">",
+                move |_: Trigger<Pointer<Click>>, mut design: ResMut<ShellDesign>| {
+                    setting.step(&mut design, stage, 1);
+                },
+            ),
+        ],
+    )
+}
+
+fn add_stage(_: Trigger<Pointer<Click>>, mut design: ResMut<ShellDesign>) {
+    design.add_stage();
+}
+
+fn remove_stage(_: Trigger<Pointer<Click>>, mut design: ResMut<ShellDesign>) {
+    design.remove_stage();
+}
+
+fn update_stage_panel(
+    design: Res<ShellDesign>,
+    mut controls_query: Query<(&StageControls, &mut Node)>,
+    mut label_query: Query<(&StageValueLabel, &mut Text)>,
+) {
+    let stages = design.stages();
+    for (controls, mut node) in &mut controls_query {
+        let last = controls.stage + usize::from(controls.needs_next_stage);
+        node.display = if last < stages.len() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    for (label, mut text) in &mut label_query {
+        if let Some(stage) = stages.get(label.stage) {
+            text.0 = label.setting.value(stage);
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CreditsLabel;
+
+/// Shows the level of an upgrade and what the next one costs.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct UpgradeLabel(Upgrade);
+
+/// Spends credits on launcher upgrades.
+fn upgrade_panel() -> impl Bundle {
+    (
+        Name::new("Upgrade Panel"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Px(20.0),
+            left: Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(6.0),
+            ..default()
+        },
+        Children::spawn((
+            Spawn((widget::label(""), CreditsLabel)),
+            SpawnIter(Upgrade::ALL.into_iter().map(upgrade_row)),
+        )),
+    )
+}
+
+fn upgrade_row(upgrade: Upgrade) -> impl Bundle {
+    (
+        Name::new("Upgrade Row"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: Px(200.0),
+                    ..default()
+                },
+                children![widget::label(upgrade.name())],
+            ),
+            (
+                Node {
+                    width: Px(140.0),
+                    ..default()
+                },
+                children![(widget::label(""), UpgradeLabel(upgrade))],
+            ),
+            widget::button_small(
+                "+",
+                move |_: Trigger<Pointer<Click>>, mut progress: ResMut<Progress>| {
+                    progress.buy(upgrade);
+                },
+            ),
+        ],
+    )
+}
+
+fn update_upgrade_labels(
+    progress: Res<Progress>,
+    mut credits_label: Single<&mut Text, With<CreditsLabel>>,
+    mut label_query: Query<(&UpgradeLabel, &mut Text), Without<CreditsLabel>>,
+) {
+    credits_label.0 = format!("Credits: {}", progress.credits);
+    for (label, mut text) in &mut label_query {
+        let level = progress.upgrades.level(label.0);
+        text.0 = match progress.cost(label.0) {
+            Some(cost) => format!("{level}/{} ({cost})", label.0.max_level()),
+            None => "Max".to_string(),
+        };
+    }
+}
+
 fn create_menu_side_panel(asset_server: &Res<AssetServer>) -> impl Bundle {
     let workspace_sidebar = asset_server.load("images/workspace-panel.png");
 
@@ -94,7 +623,21 @@ fn create_menu_side_panel(asset_server: &Res<AssetServer>) -> impl Bundle {
     )
 }
 
-fn spawn_workshop(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn spawn_workshop(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    daily_challenge: Option<Res<DailyChallenge>>,
+    daily_scores: Res<DailyScores>,
+    mut selected: ResMut<SelectedBit>,
+) {
+    // The daily challenge only allows some of the bits.
+    let bits = daily_challenge
+        .as_ref()
+        .map_or(&Bit::ALL[..], |challenge| &challenge.bits);
+    if !bits.contains(&selected.0) {
+        selected.0 = bits[0];
+    }
+
     commands
         .spawn((
             Node {
@@ -111,14 +654,205 @@ fn spawn_workshop(mut commands: Commands, asset_server: Res<AssetServer>) {
             Pickable::IGNORE,
         ))
         .with_children(|parent| {
-            parent.spawn(create_menu_side_panel(&asset_server));
-            parent.spawn(create_menu_panel(&asset_server));
+            parent
+                .spawn(create_menu_side_panel(&asset_server))
+                .with_children(|panel| spawn_bit_palette(panel, &asset_server, bits));
+            parent
+                .spawn(create_menu_panel(&asset_server))
+                .with_children(spawn_shell_grid);
         });
+    commands.spawn((mix_panel(), StateScoped(Screen::Workshop)));
+    commands.spawn((stage_panel(), StateScoped(Screen::Workshop)));
+    commands.spawn((upgrade_panel(), StateScoped(Screen::Workshop)));
 
     commands.spawn((
         widget::button("Launch bits!", launch_bits),
         StateScoped(Screen::Workshop),
     ));
+    // The daily challenge has the same rules for everyone.
+    match daily_challenge {
+        Some(challenge) => {
+            commands.spawn((
+                daily_challenge_panel(&challenge, &daily_scores),
+                StateScoped(Screen::Workshop),
+            ));
+        }
+        None => {
+            commands.spawn((difficulty_widget(), StateScoped(Screen::Workshop)));
+            commands.spawn((game_mode_widget(), StateScoped(Screen::Workshop)));
+            commands.spawn((coop_mode_widget(), StateScoped(Screen::Workshop)));
+        }
+    }
+}
+
+/// Shows the day's modifier and how previous attempts went.
+fn daily_challenge_panel(challenge: &DailyChallenge, scores: &DailyScores) -> impl Bundle {
+    let best_today = match scores.best(challenge.date) {
+        Some(score) => format!("Best today: {score}"),
+        None => "No score yet today".to_string(),
+    };
+    let recent = scores
+        .recent(RECENT_DAILY_SCORES)
+        .map(|(date, score)| widget::label(format!("{date}: {score}")))
+        .collect::<Vec<_>>();
+    (
+        Name::new("Daily Challenge Panel"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(100.0),
+            left: Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(10.0),
+            ..default()
+        },
+        Children::spawn((
+            Spawn(widget::label(format!("Daily Challenge {}", challenge.date))),
+            Spawn(widget::label(format!(
+                "Modifier: {}",
+                challenge.modifier.name()
+            ))),
+            Spawn(widget::label(best_today)),
+            SpawnIter(recent.into_iter()),
+        )),
+    )
+}
+
+/// Picks the rules preset for the next round.
+fn difficulty_widget() -> impl Bundle {
+    (
+        Name::new("Difficulty Widget"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(100.0),
+            left: Px(20.0),
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            widget::label("Difficulty"),
+            widget::button_small("<", previous_difficulty),
+            (
+                Name::new("Current Difficulty"),
+                Node {
+                    width: Px(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), DifficultyLabel)],
+            ),
+            widget::button_small(">", next_difficulty),
+        ],
+    )
+}
+
+fn previous_difficulty(_: Trigger<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.previous();
+}
+
+fn next_difficulty(_: Trigger<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DifficultyLabel;
+
+fn update_difficulty_label(
+    difficulty: Res<Difficulty>,
+    mut label: Single<&mut Text, With<DifficultyLabel>>,
+) {
+    label.0 = difficulty.name().to_string();
+}
+
+/// Picks the kind of round to play next.
+fn game_mode_widget() -> impl Bundle {
+    (
+        Name::new("Game Mode Widget"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(160.0),
+            left: Px(20.0),
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            widget::label("Mode"),
+            widget::button_small("<", previous_game_mode),
+            (
+                Name::new("Current Game Mode"),
+                Node {
+                    width: Px(160.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), GameModeLabel)],
+            ),
+            widget::button_small(">", next_game_mode),
+        ],
+    )
+}
+
+fn previous_game_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<GameMode>) {
+    *mode = mode.previous();
+}
+
+fn next_game_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<GameMode>) {
+    *mode = mode.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GameModeLabel;
+
+fn update_game_mode_label(mode: Res<GameMode>, mut label: Single<&mut Text, With<GameModeLabel>>) {
+    label.0 = mode.name().to_string();
+}
+
+/// Picks whether a second player joins in, and how the players score.
+fn coop_mode_widget() -> impl Bundle {
+    (
+        Name::new("Co-op Mode Widget"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(220.0),
+            left: Px(20.0),
+            align_items: AlignItems::Center,
+            column_gap: Px(10.0),
+            ..default()
+        },
+        children![
+            widget::label("Players"),
+            widget::button_small("<", previous_coop_mode),
+            (
+                Name::new("Current Co-op Mode"),
+                Node {
+                    width: Px(120.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), CoopModeLabel)],
+            ),
+            widget::button_small(">", next_coop_mode),
+        ],
+    )
+}
+
+fn previous_coop_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<CoopMode>) {
+    *mode = mode.previous();
+}
+
+fn next_coop_mode(_: Trigger<Pointer<Click>>, mut mode: ResMut<CoopMode>) {
+    *mode = mode.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CoopModeLabel;
+
+fn update_coop_mode_label(mode: Res<CoopMode>, mut label: Single<&mut Text, With<CoopModeLabel>>) {
+    label.0 = mode.name().to_string();
 }
 
 fn launch_bits(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
@@ -0,0 +1,202 @@
+//! Sprite sheet metadata loaded from `.atlas.ron` files next to the images, so frame layouts
+//! and animation timing live in data instead of code.
+//!
+//! Each atlas creates its [`TextureAtlasLayout`] once when it's loaded, and every sprite using
+//! the atlas shares it.
+
+use std::{collections::HashMap, error::Error, num::NonZeroU64, time::Duration};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::Deserialize;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<SpriteAtlas>();
+    app.init_asset_loader::<SpriteAtlasLoader>();
+    app.register_type::<AtlasAnimation>();
+}
+
+/// The frames of a sprite sheet and the animations made from them.
+#[derive(Asset, TypePath, Debug)]
+pub struct SpriteAtlas {
+    /// The frame layout, shared by every sprite using this atlas.
+    pub layout: Handle<TextureAtlasLayout>,
+    animations: HashMap<String, AtlasAnimation>,
+}
+
+impl SpriteAtlas {
+    /// A [`TextureAtlas`] showing the given frame.
+    pub fn texture_atlas(&self, index: usize) -> TextureAtlas {
+        TextureAtlas {
+            layout: self.layout.clone(),
+            index,
+        }
+    }
+
+    pub fn animation(&self, name: &str) -> Option<&AtlasAnimation> {
+        self.animations.get(name)
+    }
+}
+
+/// A named sequence of atlas frames.
+#[derive(Reflect, Deserialize, Debug, Clone)]
+pub struct AtlasAnimation {
+    /// Indices into the atlas, in playback order.
+    frames: Vec<usize>,
+    /// How long each frame is shown, in milliseconds. Frames shown for no time at all are
+    /// rejected when the atlas is loaded.
+    frame_millis: NonZeroU64,
+    #[serde(default)]
+    mode: LoopMode,
+    /// Events sent when the animation reaches a frame, by position in `frames`.
+    #[serde(default)]
+    events: HashMap<usize, String>,
+}
+
+/// What an animation does after its last frame.
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    /// Start over from the first frame.
+    #[default]
+    Loop,
+    /// Stay on the last frame.
+    Once,
+    /// Play backwards to the first frame, then forwards again.
+    PingPong,
+}
+
+impl AtlasAnimation {
+    pub fn frame_duration(&self) -> Duration {
+        Duration::from_millis(self.frame_millis.get())
+    }
+
+    /// The number of steps before the animation repeats (or ends, if it only plays once).
+    fn cycle_len(&self) -> usize {
+        let frames = self.frames.len().max(1);
+        match self.mode {
+            LoopMode::PingPong if frames > 1 => 2 * frames - 2,
+            _ => frames,
+        }
+    }
+
+    /// The step that follows `step`.
+    pub fn next_step(&self, step: usize) -> usize {
+        match self.mode {
+            LoopMode::Once => (step + 1).min(self.cycle_len() - 1),
+            LoopMode::Loop | LoopMode::PingPong => (step + 1) % self.cycle_len(),
+        }
+    }
+
+    /// The position in `frames` at the given step.
+    fn position(&self, step: usize) -> usize {
+        let frames = self.frames.len();
+        if step < frames {
+            step
+        } else {
+            // Playing backwards during a ping-pong.
+            (2 * frames).saturating_sub(step + 2)
+        }
+    }
+
+    /// The atlas index to show at the given step.
+    pub fn atlas_index(&self, step: usize) -> usize {
+        self.frames
+            .get(self.position(step))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The event to send when the animation reaches the given step, if any.
+    pub fn event_at(&self, step: usize) -> Option<&str> {
+        self.events.get(&self.position(step)).map(String::as_str)
+    }
+}
+
+/// The contents of an `.atlas.ron` file.
+#[derive(Deserialize)]
+struct AtlasFile {
+    frames: AtlasFrames,
+    #[serde(default)]
+    animations: HashMap<String, AtlasAnimation>,
+}
+
+/// Where the frames are in the image.
+#[derive(Deserialize)]
+enum AtlasFrames {
+    /// Frames of equal size, laid out in rows.
+    Grid {
+        tile_size: (u32, u32),
+        columns: u32,
+        rows: u32,
+        /// The gap between frames.
+        #[serde(default)]
+        padding: (u32, u32),
+        /// The gap between the top left corner of the image and the first frame.
+        #[serde(default)]
+        offset: (u32, u32),
+    },
+    /// Frames at arbitrary positions, given as `(min_x, min_y, max_x, max_y)` in pixels.
+    Rects {
+        image_size: (u32, u32),
+        rects: Vec<(u32, u32, u32, u32)>,
+    },
+}
+
+impl AtlasFrames {
+    fn layout(self) -> TextureAtlasLayout {
+        match self {
+            Self::Grid {
+                tile_size,
+                columns,
+                rows,
+                padding,
+                offset,
+            } => TextureAtlasLayout::from_grid(
+                tile_size.into(),
+                columns,
+                rows,
+                Some(padding.into()),
+                Some(offset.into()),
+            ),
+            Self::Rects { image_size, rects } => {
+                let mut layout = TextureAtlasLayout::new_empty(image_size.into());
+                for (min_x, min_y, max_x, max_y) in rects {
+                    layout.add_texture(URect::new(min_x, min_y, max_x, max_y));
+                }
+                layout
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SpriteAtlasLoader;
+
+impl AssetLoader for SpriteAtlasLoader {
+    type Asset = SpriteAtlas;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<SpriteAtlas, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file = ron::de::from_bytes::<AtlasFile>(&bytes)?;
+
+        let layout = load_context.add_labeled_asset("layout".to_string(), file.frames.layout());
+        Ok(SpriteAtlas {
+            layout,
+            animations: file.animations,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["atlas.ron"]
+    }
+}
@@ -0,0 +1,112 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        boss::{BossPart, BossPartKind, Mothership},
+        game_mode::{GameMode, RoundResults},
+        launcher::Projectile,
+        score::Scoreboard,
+        uap::{Shielded, Uap},
+    },
+    game_rules::GameRules,
+};
+
+/// A round on the launchpad where the mothership arrives after a second, without any drones
+/// or other UAPs.
+fn boss_app(mode: GameMode) -> App {
+    let mut app = test_app();
+    app.insert_resource(GameRules {
+        max_uaps: 0,
+        boss_secs: 1.0,
+        boss_health: 100.0,
+        ..default()
+    });
+    app.insert_resource(mode);
+    set_screen(&mut app, Screen::Launchpad);
+    app
+}
+
+fn parts(app: &mut App, kind: BossPartKind) -> Vec<Entity> {
+    app.world_mut()
+        .query::<(Entity, &BossPart)>()
+        .iter(app.world())
+        .filter(|(_, part)| part.kind == kind)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn score(app: &mut App) -> usize {
+    app.world_mut()
+        .query::<&Scoreboard>()
+        .single(app.world())
+        .unwrap()
+        .score()
+}
+
+#[test]
+fn the_mothership_makes_a_shielded_entrance() {
+    let mut app = boss_app(GameMode::Campaign);
+    advance(&mut app, 1.1);
+    assert_eq!(count::<Mothership>(&mut app), 1);
+    assert_eq!(count::<BossPart>(&mut app), 7);
+    assert_eq!(count::<Shielded>(&mut app), 7);
+    // The parts share the boss health bar rather than having bars of their own.
+    let health_bars = app
+        .world_mut()
+        .query::<&Name>()
+        .iter(app.world())
+        .filter(|name| name.as_str() == "Health Bar")
+        .count();
+    assert_eq!(health_bars, 0);
+
+    // A shell straight up meets the core on its way down, and bursts against its shield.
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 2.0);
+    assert_eq!(count::<Projectile>(&mut app), 0);
+    let core = parts(&mut app, BossPartKind::Core)[0];
+    assert_eq!(app.world().get::<Uap>(core).unwrap().health_fraction(), 1.0);
+
+    // Once it has arrived, only the core stays shielded.
+    advance(&mut app, 3.0);
+    assert_eq!(count::<Shielded>(&mut app), 1);
+    assert!(app.world().entity(core).contains::<Shielded>());
+}
+
+#[test]
+fn the_core_is_exposed_by_the_generators_and_brings_the_ship_down() {
+    let mut app = boss_app(GameMode::Campaign);
+    advance(&mut app, 6.0);
+    assert_eq!(count::<Shielded>(&mut app), 1);
+
+    for generator in parts(&mut app, BossPartKind::ShieldGenerator) {
+        app.world_mut().despawn(generator);
+    }
+    advance(&mut app, 0.1);
+    assert_eq!(count::<Shielded>(&mut app), 0);
+
+    let score_before = score(&mut app);
+    let core = parts(&mut app, BossPartKind::Core)[0];
+    app.world_mut().despawn(core);
+    advance(&mut app, 0.1);
+    assert_eq!(count::<Mothership>(&mut app), 0);
+    assert_eq!(count::<BossPart>(&mut app), 0);
+    // Bringing it down clears the level.
+    assert_eq!(
+        *app.world().resource::<State<Screen>>().get(),
+        Screen::Results
+    );
+    let results = app.world().resource::<RoundResults>();
+    assert_eq!(results.mode, GameMode::Campaign);
+    assert_eq!(results.reason, "Level cleared");
+    assert_eq!(results.score, score_before + 10);
+}
+
+#[test]
+fn only_campaign_levels_end_with_a_mothership() {
+    for mode in [GameMode::FreePlay, GameMode::Endless, GameMode::TimeAttack] {
+        let mut app = boss_app(mode);
+        advance(&mut app, 2.0);
+        assert_eq!(count::<Mothership>(&mut app), 0);
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        coop::{CoopMode, PlayerId},
+        launcher::{Launcher, Projectile},
+        score::Scoreboard,
+        uap::Uap,
+    },
+    game_rules::GameRules,
+};
+
+/// An app on the launchpad with two players, that doesn't spawn UAPs by itself.
+fn coop_app(mode: CoopMode) -> App {
+    let mut app = test_app();
+    app.insert_resource(mode);
+    app.insert_resource(GameRules {
+        max_uaps: 0,
+        uap_speed: 0.0,
+        uap_health: 20.0,
+        projectile_damage: 20.0,
+        launchers: 1,
+        ..default()
+    });
+    set_screen(&mut app, Screen::Launchpad);
+    app
+}
+
+#[test]
+fn each_player_fires_from_their_own_launcher() {
+    let mut app = coop_app(CoopMode::Shared);
+    // One launcher each, even though the rules call for a single one.
+    assert_eq!(count::<Launcher>(&mut app), 2);
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    tap_key(&mut app, KeyCode::Enter, Key::Enter);
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&Sprite, With<Projectile>>();
+    let mut colors = query
+        .iter(app.world())
+        .map(|sprite| sprite.color)
+        .collect::<Vec<_>>();
+    colors.sort_by(|a, b| a.to_srgba().red.total_cmp(&b.to_srgba().red));
+    assert_eq!(colors, [PlayerId::One.color(), PlayerId::Two.color()]);
+}
+
+#[test]
+fn versus_credits_kills_to_the_player_who_made_them() {
+    let mut app = coop_app(CoopMode::Competitive);
+    // Player two's launcher is on the right.
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&Transform, With<Launcher>>();
+    let x = query
+        .iter(app.world())
+        .map(|transform| transform.translation.x)
+        .fold(f32::MIN, f32::max);
+    let rules = app.world().resource::<GameRules>().clone();
+    app.world_mut().spawn((
+        Uap::regular(&rules, &mut rand::thread_rng()),
+        Sprite::default(),
+        Transform::from_xyz(x, 0.0, 0.0),
+    ));
+
+    tap_key(&mut app, KeyCode::Enter, Key::Enter);
+    advance(&mut app, 1.5);
+    let scoreboard = app
+        .world_mut()
+        .query::<&Scoreboard>()
+        .single(app.world())
+        .unwrap();
+    assert_eq!(scoreboard.score(), 1);
+    assert_eq!(scoreboard.player_score(PlayerId::One), 0);
+    assert_eq!(scoreboard.player_score(PlayerId::Two), 1);
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use super::*;
+use crate::{
+    app::{
+        bits::{Bit, DamageTag, ShellDesign},
+        daily_challenge::{DailyChallenge, StartDailyChallenge},
+        game_mode::GameMode,
+    },
+    game_rules::{Difficulty, GameRules, GameRulesAsset, RulesModifier},
+    rng::{Date, RoundSeed},
+};
+
+#[test]
+fn daily_challenge_sets_up_the_day_and_leaving_it_restores_the_rules() {
+    let mut app = test_app();
+    app.insert_resource(Difficulty::Hard);
+    app.update();
+    let hard_rules = app.world().resource::<GameRules>().clone();
+    let mut design = ShellDesign::empty();
+    for (cell, bit) in Bit::ALL.into_iter().enumerate() {
+        design.set(cell, Some(bit));
+    }
+    app.insert_resource(design.clone());
+
+    let date = Date::parse("2026-10-19").unwrap();
+    let modifier = DailyChallenge::modifier_for(date);
+    let bits = DailyChallenge::bits_for(date);
+    assert_eq!(bits, DailyChallenge::bits_for(date));
+    for tag in DamageTag::ALL {
+        assert_eq!(bits.iter().filter(|bit| bit.tag() == tag).count(), 1);
+    }
+    app.world_mut().commands().queue(StartDailyChallenge(date));
+    set_screen(&mut app, Screen::Workshop);
+
+    assert_eq!(*app.world().resource::<RoundSeed>(), RoundSeed::Daily(date));
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Normal);
+    assert_eq!(*app.world().resource::<GameMode>(), GameMode::Endless);
+    assert_eq!(*app.world().resource::<RulesModifier>(), modifier);
+    assert_eq!(app.world().resource::<DailyChallenge>().bits, bits);
+    let challenge_bits = app
+        .world()
+        .resource::<ShellDesign>()
+        .bits()
+        .collect::<Vec<_>>();
+    assert_eq!(challenge_bits, bits);
+    let rules_assets = app.world().resource::<Assets<GameRulesAsset>>();
+    let mut expected = rules_assets
+        .iter()
+        .next()
+        .and_then(|(_, asset)| asset.preset(Difficulty::Normal))
+        .unwrap()
+        .clone();
+    modifier.apply(&mut expected);
+    assert_eq!(*app.world().resource::<GameRules>(), expected);
+
+    set_screen(&mut app, Screen::Title);
+    app.update();
+    assert!(!app.world().contains_resource::<DailyChallenge>());
+    assert_eq!(*app.world().resource::<RoundSeed>(), RoundSeed::Random);
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Hard);
+    assert_eq!(*app.world().resource::<GameMode>(), GameMode::FreePlay);
+    assert_eq!(*app.world().resource::<GameRules>(), hard_rules);
+    assert_eq!(*app.world().resource::<ShellDesign>(), design);
+}
@@ -0,0 +1,115 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        bits::{Bit, DamageTag, Defences, ShellDesign},
+        uap::{Hitbox, Uap, UapAssets},
+    },
+    game_rules::GameRules,
+    sprite_atlas::SpriteAtlas,
+};
+
+/// A launchpad round with a hovering UAP with the given defences right above the launcher.
+fn defended_uap_app(defences: Defences) -> (App, Entity) {
+    let mut app = test_app();
+    let rules = GameRules {
+        max_uaps: 0,
+        uap_speed: 0.0,
+        uap_health: 100.0,
+        projectile_damage: 20.0,
+        ..default()
+    };
+    app.insert_resource(rules.clone());
+    set_screen(&mut app, Screen::Launchpad);
+    let uap = app
+        .world_mut()
+        .spawn((
+            Uap::regular(&rules, &mut rand::thread_rng()).with_defences(defences),
+            Sprite::default(),
+            Transform::from_xyz(0.0, 0.0, 0.0),
+        ))
+        .id();
+    (app, uap)
+}
+
+/// Fire a shell packed with nothing but `bit` and return the UAP's health afterwards.
+fn fire_shell_of(app: &mut App, uap: Entity, bit: Bit) -> f32 {
+    let mut design = ShellDesign::empty();
+    design.set(0, Some(bit));
+    app.insert_resource(design);
+    tap_key(app, KeyCode::Space, Key::Space);
+    advance(app, 1.5);
+    app.world().get::<Uap>(uap).unwrap().health_fraction()
+}
+
+#[test]
+fn shields_only_let_their_own_flavour_through() {
+    let (mut app, uap) = defended_uap_app(Defences {
+        shield: Some(DamageTag::Citrus),
+        ..default()
+    });
+
+    assert_eq!(fire_shell_of(&mut app, uap, Bit::PepperRed), 1.0);
+    assert_eq!(fire_shell_of(&mut app, uap, Bit::Cookie), 1.0);
+    assert!((fire_shell_of(&mut app, uap, Bit::Lemon) - 0.8).abs() < 1e-4);
+}
+
+#[test]
+fn weaknesses_double_and_resistances_halve_damage() {
+    let (mut app, uap) = defended_uap_app(Defences {
+        resistance: Some(DamageTag::Sweet),
+        weakness: Some(DamageTag::Spicy),
+        ..default()
+    });
+
+    assert!((fire_shell_of(&mut app, uap, Bit::PepperGreen) - 0.6).abs() < 1e-4);
+    assert!((fire_shell_of(&mut app, uap, Bit::Honey) - 0.5).abs() < 1e-4);
+    assert!((fire_shell_of(&mut app, uap, Bit::Pineapple) - 0.3).abs() < 1e-4);
+}
+
+/// Destroy a UAP above the launcher with a shell of `bit`, and return the health of a UAP
+/// shielded against everything but citrus that lies wide across the path of the falling debris.
+fn health_under_debris_of(bit: Bit) -> f32 {
+    let (mut app, uap) = defended_uap_app(Defences::default());
+    let uap_assets = app.world().resource::<UapAssets>().clone();
+    let (sprite, animator) =
+        uap_assets.animated_sprite(app.world().resource::<Assets<SpriteAtlas>>());
+    let rules = app.world().resource::<GameRules>().clone();
+    // One shell's worth of health, with a sprite to break into fragments.
+    let doomed = GameRules {
+        uap_health: rules.projectile_damage,
+        ..rules.clone()
+    };
+    let world = app.world_mut();
+    world.entity_mut(uap).insert((
+        Uap::regular(&doomed, &mut rand::thread_rng()),
+        sprite,
+        animator,
+    ));
+    let shielded = world
+        .spawn((
+            Uap::regular(&rules, &mut rand::thread_rng()).with_defences(Defences {
+                shield: Some(DamageTag::Citrus),
+                ..default()
+            }),
+            Sprite::default(),
+            // Clear of the shell's path, but wide enough to catch the fragments flying right.
+            Hitbox(Vec2::new(900.0, 40.0)),
+            Transform::from_xyz(500.0, -200.0, 0.0),
+        ))
+        .id();
+
+    let mut design = ShellDesign::empty();
+    design.set(0, Some(bit));
+    app.insert_resource(design);
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 3.0);
+    app.world().get::<Uap>(shielded).unwrap().health_fraction()
+}
+
+#[test]
+fn debris_carries_the_flavours_of_the_shell_that_made_it() {
+    assert_eq!(health_under_debris_of(Bit::PepperRed), 1.0);
+    assert!(health_under_debris_of(Bit::Lemon) < 1.0);
+}
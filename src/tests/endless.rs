@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+
+use super::*;
+use crate::{
+    app::{
+        endless::{Lives, UapEscapedEvent},
+        game_mode::{GameMode, RoundResults},
+        high_scores::HighScores,
+        score::ScoreEvent,
+        uap::Uap,
+        uap_spawner::{endless_intensity, endless_rules},
+    },
+    game_rules::GameRules,
+};
+
+/// An endless round on the launchpad that doesn't spawn UAPs by itself.
+fn endless_app() -> App {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<GameRules>().max_uaps = 0;
+    app.insert_resource(GameMode::Endless);
+    set_screen(&mut app, Screen::Launchpad);
+    app
+}
+
+/// Spawn a UAP that's about to fly off the top of the arena, under a spawner of its own like
+/// spawned UAPs have. Returns the spawner.
+fn spawn_escaping_uap(app: &mut App) -> Entity {
+    let rules = app.world().resource::<GameRules>().clone();
+    app.world_mut()
+        .spawn((Transform::default(), Visibility::default()))
+        .with_child((
+            Uap::regular(&rules, &mut rand::thread_rng()).with_drift(600.0),
+            Transform::from_xyz(0.0, 580.0, 0.0),
+        ))
+        .id()
+}
+
+#[test]
+fn escaping_uaps_cost_lives_until_the_round_ends() {
+    let mut app = endless_app();
+    assert_eq!(*app.world().resource::<Lives>(), Lives(3));
+
+    let spawner = spawn_escaping_uap(&mut app);
+    advance(&mut app, 0.5);
+    assert_eq!(count::<Uap>(&mut app), 0);
+    assert!(app.world().get_entity(spawner).is_err());
+    assert_eq!(*app.world().resource::<Lives>(), Lives(2));
+
+    spawn_escaping_uap(&mut app);
+    spawn_escaping_uap(&mut app);
+    advance(&mut app, 0.5);
+    assert_eq!(
+        *app.world().resource::<State<Screen>>().get(),
+        Screen::Results
+    );
+    assert!(!app.world().contains_resource::<Lives>());
+    let results = app.world().resource::<RoundResults>();
+    assert_eq!(results.mode, GameMode::Endless);
+    assert_eq!(results.score, 0);
+    // Rounds that scored nothing don't make the high scores.
+    assert_eq!(results.place, None);
+    assert!(
+        app.world()
+            .resource::<HighScores>()
+            .of(GameMode::Endless)
+            .is_empty()
+    );
+}
+
+#[test]
+fn points_scored_as_the_last_life_is_lost_still_count() {
+    let mut app = endless_app();
+    app.insert_resource(Lives(1));
+    // A kill on the tick the last UAP got away.
+    app.world_mut().send_event(UapEscapedEvent);
+    app.world_mut().send_event(ScoreEvent {
+        score_to_add: 1,
+        player: None,
+    });
+    advance(&mut app, 0.1);
+    let results = app.world().resource::<RoundResults>();
+    assert_eq!(results.reason, "Out of lives");
+    assert_eq!(results.score, 1);
+}
+
+#[test]
+fn free_play_has_no_lives() {
+    let mut app = test_app();
+    set_screen(&mut app, Screen::Launchpad);
+    assert!(!app.world().contains_resource::<Lives>());
+}
+
+#[test]
+fn endless_rounds_ramp_up_towards_a_limit() {
+    assert_eq!(endless_intensity(0.0), 0.0);
+    let intensities = [30.0, 120.0, 600.0, 1200.0].map(endless_intensity);
+    assert!(intensities.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(intensities.iter().all(|&intensity| intensity < 1.0));
+    assert!(intensities[3] > 0.99);
+
+    let rules = GameRules::default();
+    assert_eq!(endless_rules(&rules, 0.0), rules);
+    let mut previous = rules.clone();
+    for secs in [60.0, 300.0, 3600.0] {
+        let ramped = endless_rules(&rules, secs);
+        assert!(ramped.uap_spawn_secs < previous.uap_spawn_secs);
+        assert!(ramped.max_uaps >= previous.max_uaps);
+        assert!(ramped.uap_speed > previous.uap_speed);
+        assert!(ramped.tough_uap_speed > previous.tough_uap_speed);
+        assert_eq!(ramped.uap_health, rules.uap_health);
+        previous = ramped;
+    }
+    assert!(previous.max_uaps > rules.max_uaps);
+}
@@ -0,0 +1,193 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        launcher::{ActiveLauncher, Launcher, Projectile},
+        score::ScoreEvent,
+        uap::{DestroyUapEvent, Uap},
+        upgrades::{Progress, Upgrade},
+    },
+    game_rules::GameRules,
+};
+
+/// An app on the launchpad that doesn't spawn UAPs by itself.
+fn launchpad_app() -> App {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<GameRules>().max_uaps = 0;
+    set_screen(&mut app, Screen::Launchpad);
+    app
+}
+
+#[test]
+fn space_fires_a_shell() {
+    let mut app = launchpad_app();
+    assert_eq!(count::<Projectile>(&mut app), 0);
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    assert_eq!(count::<Projectile>(&mut app), 1);
+
+    // Not before the launcher has reloaded.
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    assert_eq!(count::<Projectile>(&mut app), 1);
+
+    let reload_secs = app.world().resource::<GameRules>().launcher_reload_secs;
+    advance(&mut app, reload_secs);
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    assert_eq!(count::<Projectile>(&mut app), 2);
+}
+
+#[test]
+fn uap_is_destroyed_by_five_hits() {
+    let mut app = launchpad_app();
+    count_events::<DestroyUapEvent>(&mut app);
+    count_events::<ScoreEvent>(&mut app);
+
+    let rules = GameRules {
+        max_uaps: 0,
+        uap_speed: 0.0,
+        uap_health: 100.0,
+        projectile_damage: 20.0,
+        ..default()
+    };
+    app.insert_resource(rules.clone());
+    // Hovering right above the upright launcher.
+    let uap = app
+        .world_mut()
+        .spawn((
+            Uap::regular(&rules, &mut rand::thread_rng()),
+            Sprite::default(),
+            Transform::from_xyz(0.0, 0.0, 0.0),
+        ))
+        .id();
+
+    for hit in 1..=4 {
+        tap_key(&mut app, KeyCode::Space, Key::Space);
+        advance(&mut app, 1.5);
+        let health = app.world().get::<Uap>(uap).unwrap().health_fraction();
+        assert!((health - (1.0 - 0.2 * hit as f32)).abs() < 1e-4);
+        assert_eq!(event_count::<DestroyUapEvent>(&app), 0);
+    }
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 1.5);
+    assert!(app.world().get_entity(uap).is_err());
+    assert_eq!(event_count::<DestroyUapEvent>(&app), 1);
+    assert_eq!(event_count::<ScoreEvent>(&app), 1);
+}
+
+/// The horizontal position of the launcher being operated, and the rotations of every launcher.
+fn active_launcher(app: &mut App) -> (f32, Vec<Quat>) {
+    let mut query = app
+        .world_mut()
+        .query::<(&Transform, Has<ActiveLauncher>, &Launcher)>();
+    let mut active = None;
+    let mut rotations = Vec::new();
+    for (transform, is_active, _) in query.iter(app.world()) {
+        if is_active {
+            active = Some(transform.translation.x);
+        }
+        rotations.push(transform.rotation);
+    }
+    (active.expect("a launcher is active"), rotations)
+}
+
+#[test]
+fn tab_hands_control_to_the_next_launcher() {
+    let mut app = test_app();
+    app.insert_resource(GameRules {
+        max_uaps: 0,
+        launchers: 3,
+        ..default()
+    });
+    set_screen(&mut app, Screen::Launchpad);
+    assert_eq!(count::<Launcher>(&mut app), 3);
+    assert_eq!(active_launcher(&mut app).0, 0.0);
+
+    tap_key(&mut app, KeyCode::Tab, Key::Tab);
+    let (x, _) = active_launcher(&mut app);
+    assert!(x > 0.0);
+
+    // From the rightmost launcher back round to the leftmost.
+    tap_key(&mut app, KeyCode::Tab, Key::Tab);
+    let (x, before) = active_launcher(&mut app);
+    assert!(x < 0.0);
+
+    // Only the launcher being operated turns.
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::KeyA);
+    advance(&mut app, 0.2);
+    let (_, after) = active_launcher(&mut app);
+    let turned = before.iter().zip(&after).filter(|(a, b)| a != b).count();
+    assert_eq!(turned, 1);
+}
+
+#[test]
+fn launchers_are_built_with_the_upgrades_bought() {
+    let mut app = test_app();
+    app.insert_resource(Progress {
+        credits: 70,
+        ..default()
+    });
+    app.world_mut().resource_mut::<GameRules>().max_uaps = 0;
+    {
+        let mut progress = app.world_mut().resource_mut::<Progress>();
+        assert!(progress.buy(Upgrade::DoubleBarrel));
+        assert!(progress.buy(Upgrade::Reload));
+        assert_eq!(progress.credits, 20);
+        // Neither enough credits for the next level nor a next level to buy.
+        assert!(!progress.buy(Upgrade::Reload));
+        assert!(!progress.buy(Upgrade::DoubleBarrel));
+    }
+    set_screen(&mut app, Screen::Launchpad);
+
+    let upgrades = app.world().resource::<Progress>().upgrades;
+    let mut query = app.world_mut().query::<&Launcher>();
+    assert!(
+        query
+            .iter(app.world())
+            .all(|launcher| launcher.upgrades == upgrades)
+    );
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    assert_eq!(count::<Projectile>(&mut app), 2);
+}
+
+/// Spawn a UAP that goes down in a single hit, hovering right above the upright launcher.
+fn spawn_fragile_uap(app: &mut App) -> Entity {
+    let rules = app.world().resource::<GameRules>().clone();
+    let rules = GameRules {
+        uap_speed: 0.0,
+        uap_health: rules.projectile_damage,
+        ..rules
+    };
+    app.world_mut()
+        .spawn((
+            Uap::regular(&rules, &mut rand::thread_rng()),
+            Sprite::default(),
+            Transform::from_xyz(0.0, 0.0, 0.0),
+        ))
+        .id()
+}
+
+#[test]
+fn shells_hitting_together_destroy_a_uap_once() {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<GameRules>().max_uaps = 0;
+    app.world_mut()
+        .resource_mut::<Progress>()
+        .upgrades
+        .double_barrel = 1;
+    set_screen(&mut app, Screen::Launchpad);
+    count_events::<DestroyUapEvent>(&mut app);
+    count_events::<ScoreEvent>(&mut app);
+    let uap = spawn_fragile_uap(&mut app);
+
+    // Both barrels' shells reach the UAP on the same tick.
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 1.5);
+    assert!(app.world().get_entity(uap).is_err());
+    assert_eq!(event_count::<DestroyUapEvent>(&app), 1);
+    assert_eq!(event_count::<ScoreEvent>(&app), 1);
+}
@@ -0,0 +1,116 @@
+//! Integration tests that run the game's plugins in a [`headless`] [`App`].
+
+mod boss;
+mod coop;
+mod daily_challenge;
+mod damage;
+mod endless;
+mod launchpad;
+mod movement;
+mod pause;
+mod replay;
+mod rng;
+mod screens;
+mod stages;
+mod time_attack;
+mod trails;
+
+use std::time::Duration;
+
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::{Pause, headless, screens::Screen};
+
+/// The time that passes with each update.
+const STEP: Duration = headless::STEP;
+
+/// How long to wait for the assets before giving up.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An app running [`GamePlugin`](crate::GamePlugin) with its assets loaded, on the title screen.
+fn test_app() -> App {
+    let mut app = headless::app();
+    assert!(
+        headless::load_assets(&mut app, LOAD_TIMEOUT),
+        "timed out loading assets"
+    );
+    set_screen(&mut app, Screen::Title);
+    app
+}
+
+/// Switch screens and run the transition.
+fn set_screen(app: &mut App, screen: Screen) {
+    app.world_mut()
+        .resource_mut::<NextState<Screen>>()
+        .set(screen);
+    app.update();
+}
+
+/// Pause or unpause the game and run the transition.
+fn set_pause(app: &mut App, paused: bool) {
+    app.world_mut()
+        .resource_mut::<NextState<Pause>>()
+        .set(Pause(paused));
+    app.update();
+}
+
+/// Keep updating until `secs` have passed.
+fn advance(app: &mut App, secs: f32) {
+    for _ in 0..(secs / STEP.as_secs_f32()).ceil() as usize {
+        app.update();
+    }
+}
+
+/// Press and release a key, as if tapped during a single frame.
+fn tap_key(app: &mut App, key_code: KeyCode, logical_key: Key) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: logical_key.clone(),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+}
+
+/// How many events of type `E` have been sent since [`count_events`] was called.
+#[derive(Resource)]
+struct EventCount<E: Event> {
+    count: usize,
+    _event: std::marker::PhantomData<E>,
+}
+
+/// Start counting events of type `E`, to be read with [`event_count`].
+fn count_events<E: Event>(app: &mut App) {
+    app.insert_resource(EventCount::<E> {
+        count: 0,
+        _event: default(),
+    });
+    app.add_systems(
+        Last,
+        |mut events: EventReader<E>, mut count: ResMut<EventCount<E>>| {
+            count.count += events.read().count();
+        },
+    );
+}
+
+fn event_count<E: Event>(app: &App) -> usize {
+    app.world().resource::<EventCount<E>>().count
+}
+
+/// The number of entities with a component of type `T`.
+fn count<T: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<T>>()
+        .iter(app.world())
+        .count()
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use super::*;
+use crate::app::{arena::Arena, movement::ScreenWrap};
+
+#[test]
+fn screen_wrap_moves_entities_to_the_opposite_edge() {
+    let mut app = test_app();
+    app.insert_resource(Arena {
+        size: Vec2::new(800.0, 600.0),
+    });
+    let mut spawn = |position: Vec3| {
+        app.world_mut()
+            .spawn((ScreenWrap, Transform::from_translation(position)))
+            .id()
+    };
+    let inside = spawn(Vec3::new(100.0, -50.0, 3.0));
+    let past_right = spawn(Vec3::new(530.0, 20.0, 0.0));
+    let past_bottom = spawn(Vec3::new(-10.0, -430.0, 0.0));
+    app.update();
+
+    let position = |entity: Entity| app.world().get::<Transform>(entity).unwrap().translation;
+    assert_eq!(position(inside), Vec3::new(100.0, -50.0, 3.0));
+    // Entities get 128 pixels past each edge before wrapping, so they're fully off screen.
+    assert_eq!(position(past_right), Vec3::new(530.0 - 1056.0, 20.0, 0.0));
+    assert_eq!(position(past_bottom), Vec3::new(-10.0, -430.0 + 856.0, 0.0));
+}
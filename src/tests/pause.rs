@@ -0,0 +1,119 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        boss::Mothership,
+        game_mode::GameMode,
+        launcher::{Launcher, Projectile},
+        time_attack::RoundClock,
+        uap::Uap,
+    },
+    game_rules::GameRules,
+    music::MusicDirector,
+};
+
+/// A round on the launchpad with a UAP flying about and a shell in the air.
+fn busy_launchpad_app() -> App {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<GameRules>().max_uaps = 0;
+    set_screen(&mut app, Screen::Launchpad);
+    let rules = app.world().resource::<GameRules>().clone();
+    app.world_mut().spawn((
+        Uap::regular(&rules, &mut rand::thread_rng()),
+        Sprite::default(),
+        Transform::from_xyz(-300.0, 200.0, 0.0),
+    ));
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 0.2);
+    app
+}
+
+/// Where every launcher, shell and UAP is.
+fn positions(app: &mut App) -> Vec<(Entity, Transform)> {
+    let mut positions = app
+        .world_mut()
+        .query_filtered::<(Entity, &Transform), Or<(With<Launcher>, With<Projectile>, With<Uap>)>>()
+        .iter(app.world())
+        .map(|(entity, transform)| (entity, *transform))
+        .collect::<Vec<_>>();
+    positions.sort_by_key(|(entity, _)| *entity);
+    positions
+}
+
+#[test]
+fn nothing_moves_while_paused() {
+    let mut app = busy_launchpad_app();
+    assert_eq!(count::<Projectile>(&mut app), 1);
+
+    set_pause(&mut app, true);
+    assert!(app.world().resource::<Time<Virtual>>().is_paused());
+    assert!(app.world().resource::<MusicDirector>().ducked);
+    let before = positions(&mut app);
+
+    // Turning and firing do nothing either.
+    tap_key(&mut app, KeyCode::KeyA, Key::Character("a".into()));
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 2.0);
+    assert_eq!(positions(&mut app), before);
+
+    set_pause(&mut app, false);
+    assert!(!app.world().resource::<MusicDirector>().ducked);
+    advance(&mut app, 0.2);
+    assert_ne!(positions(&mut app), before);
+}
+
+#[test]
+fn fire_pressed_while_paused_is_dropped() {
+    let mut app = busy_launchpad_app();
+    set_pause(&mut app, true);
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+
+    set_pause(&mut app, false);
+    advance(&mut app, 0.2);
+    assert_eq!(count::<Projectile>(&mut app), 1);
+}
+
+/// A paused round of the given mode, where UAPs spawn every half second and a campaign level's
+/// mothership arrives after a second.
+fn paused_round_app(mode: GameMode) -> App {
+    let mut app = test_app();
+    app.insert_resource(GameRules {
+        uap_spawn_secs: 0.5,
+        boss_secs: 1.0,
+        ..default()
+    });
+    app.insert_resource(mode);
+    set_screen(&mut app, Screen::Launchpad);
+    // Closing the title screen's menu unpauses the game.
+    app.update();
+    set_pause(&mut app, true);
+    app
+}
+
+#[test]
+fn round_clocks_stop_while_paused() {
+    let mut app = paused_round_app(GameMode::TimeAttack);
+    let remaining_secs = |app: &App| app.world().resource::<RoundClock>().remaining_secs;
+    let before = remaining_secs(&app);
+    advance(&mut app, 3.0);
+    assert_eq!(remaining_secs(&app), before);
+    // The spawn timer hasn't moved on either.
+    assert_eq!(count::<Uap>(&mut app), 0);
+
+    set_pause(&mut app, false);
+    advance(&mut app, 1.5);
+    assert!(remaining_secs(&app) < before);
+    assert!(count::<Uap>(&mut app) > 0);
+}
+
+#[test]
+fn the_mothership_waits_while_paused() {
+    let mut app = paused_round_app(GameMode::Campaign);
+    advance(&mut app, 3.0);
+    assert_eq!(count::<Mothership>(&mut app), 0);
+
+    set_pause(&mut app, false);
+    advance(&mut app, 1.5);
+    assert_eq!(count::<Mothership>(&mut app), 1);
+}
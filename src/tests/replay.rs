@@ -0,0 +1,110 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        launcher::Launcher,
+        replay::{Replay, ReplayPlayback, ReplaySettings},
+        score::Scoreboard,
+        uap::Uap,
+        upgrades::{LauncherUpgrades, Progress},
+    },
+    game_rules::GameRules,
+    rng::RoundSeed,
+};
+
+/// The ways the launcher turns in the recorded round, one after the other.
+const TURNS: [KeyCode; 3] = [KeyCode::ArrowLeft, KeyCode::ArrowRight, KeyCode::ArrowLeft];
+
+/// How many shells are fired while turning each way.
+const SHOTS_PER_TURN: usize = 40;
+
+/// How many updates pass from one shot to the next. Tapping the fire key takes two of them.
+const UPDATES_PER_SHOT: usize = 10;
+
+/// How many updates the recorded round lasts.
+const ROUND_UPDATES: usize = TURNS.len() * SHOTS_PER_TURN * UPDATES_PER_SHOT;
+
+/// Enter the launchpad at the start of a fixed tick, so that the same number of updates runs
+/// the same number of ticks in every app.
+fn enter_launchpad(app: &mut App) {
+    let mut time = app.world_mut().resource_mut::<Time<Fixed>>();
+    let overstep = time.overstep();
+    time.discard_overstep(overstep);
+    set_screen(app, Screen::Launchpad);
+}
+
+/// The score, and where each UAP is along with its health.
+fn round_state(app: &mut App) -> (usize, Vec<(Vec3, f32)>) {
+    let score = app
+        .world_mut()
+        .query::<&Scoreboard>()
+        .single(app.world())
+        .unwrap()
+        .score();
+    let mut uaps = app
+        .world_mut()
+        .query::<(&Transform, &Uap)>()
+        .iter(app.world())
+        .map(|(transform, uap)| (transform.translation, uap.health()))
+        .collect::<Vec<_>>();
+    uaps.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+    (score, uaps)
+}
+
+#[test]
+fn replays_play_the_recorded_round_again() {
+    let path = std::env::temp_dir().join("jank-n-bits-replay-test.ron");
+
+    let mut app = test_app();
+    app.insert_resource(RoundSeed::Fixed(11));
+    // UAPs that go down in a hit, so that the round scores. The replay keeps the rules.
+    let rules = GameRules {
+        uap_health: 10.0,
+        ..app.world().resource::<GameRules>().clone()
+    };
+    app.insert_resource(rules);
+    let upgrades = LauncherUpgrades {
+        double_barrel: 1,
+        ..default()
+    };
+    app.world_mut().resource_mut::<Progress>().upgrades = upgrades;
+    app.insert_resource(ReplaySettings {
+        record: Some(path.clone()),
+        replay: None,
+    });
+    enter_launchpad(&mut app);
+    for turn in TURNS {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        input.press(turn);
+        for _ in 0..SHOTS_PER_TURN {
+            tap_key(&mut app, KeyCode::Space, Key::Space);
+            for _ in 2..UPDATES_PER_SHOT {
+                app.update();
+            }
+        }
+    }
+    let recorded = round_state(&mut app);
+    assert!(!recorded.1.is_empty());
+    assert!(recorded.0 > 0);
+    // Leaving the launchpad saves the recording.
+    set_screen(&mut app, Screen::Workshop);
+    let replay = Replay::load(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let mut app = test_app();
+    app.insert_resource(RoundSeed::Fixed(12));
+    app.insert_resource(ReplayPlayback::new(replay));
+    let progress = app.world().resource::<Progress>().clone();
+    enter_launchpad(&mut app);
+    for _ in 0..ROUND_UPDATES {
+        app.update();
+    }
+    assert_eq!(round_state(&mut app), recorded);
+    // The launchers had the recording's upgrades, but the player's progress is left alone.
+    for launcher in app.world_mut().query::<&Launcher>().iter(app.world()) {
+        assert_eq!(launcher.upgrades, upgrades);
+    }
+    assert_eq!(*app.world().resource::<Progress>(), progress);
+}
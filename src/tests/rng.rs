@@ -0,0 +1,51 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::uap::Uap,
+    rng::{Date, RoundSeed},
+};
+
+/// Play a launchpad round with the given seed, firing now and then, and return where the UAPs
+/// ended up.
+fn play_round(seed: RoundSeed) -> Vec<Vec3> {
+    let mut app = test_app();
+    app.insert_resource(seed);
+    set_screen(&mut app, Screen::Launchpad);
+    for _ in 0..16 {
+        tap_key(&mut app, KeyCode::Space, Key::Space);
+        advance(&mut app, 0.5);
+    }
+
+    let mut positions = app
+        .world_mut()
+        .query_filtered::<&Transform, With<Uap>>()
+        .iter(app.world())
+        .map(|transform| transform.translation)
+        .collect::<Vec<_>>();
+    positions.sort_by(|a, b| a.x.total_cmp(&b.x));
+    positions
+}
+
+#[test]
+fn rounds_with_the_same_seed_play_out_the_same() {
+    let positions = play_round(RoundSeed::Fixed(7));
+    assert!(!positions.is_empty());
+    assert_eq!(play_round(RoundSeed::Fixed(7)), positions);
+    assert_ne!(play_round(RoundSeed::Fixed(8)), positions);
+}
+
+#[test]
+fn daily_seeds_change_with_the_date() {
+    let date = Date::parse("2026-10-19").unwrap();
+    assert_eq!(date.to_string(), "2026-10-19");
+    assert_eq!(RoundSeed::Daily(date).seed(), date.seed());
+    assert_ne!(date.seed(), Date::parse("2026-10-20").unwrap().seed());
+    assert_eq!(Date::parse("2026-13-01"), None);
+    assert_eq!(Date::parse("2026-02-31"), None);
+    assert_eq!(Date::parse("2026-04-31"), None);
+    assert_eq!(Date::parse("2026-02-29"), None);
+    assert_eq!(Date::parse("1900-02-29"), None);
+    assert!(Date::parse("2028-02-29").is_some());
+    assert!(Date::parse("2000-02-29").is_some());
+}
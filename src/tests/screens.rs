@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use super::*;
+use crate::{
+    app::{launcher::Launcher, uap::Uap},
+    game_rules::GameRules,
+};
+
+/// The number of entities that are despawned when leaving `screen`.
+fn scoped_to(app: &mut App, screen: Screen) -> usize {
+    app.world_mut()
+        .query::<&StateScoped<Screen>>()
+        .iter(app.world())
+        .filter(|scoped| scoped.0 == screen)
+        .count()
+}
+
+#[test]
+fn leaving_the_workshop_despawns_it() {
+    let mut app = test_app();
+    set_screen(&mut app, Screen::Workshop);
+    assert!(scoped_to(&mut app, Screen::Workshop) > 0);
+
+    set_screen(&mut app, Screen::Title);
+    assert_eq!(scoped_to(&mut app, Screen::Workshop), 0);
+}
+
+#[test]
+fn leaving_the_launchpad_despawns_launchers_and_uaps() {
+    let mut app = test_app();
+    set_screen(&mut app, Screen::Launchpad);
+    advance(&mut app, 5.0);
+    assert!(scoped_to(&mut app, Screen::Launchpad) > 0);
+    let launchers = app.world().resource::<GameRules>().launchers;
+    assert_eq!(count::<Launcher>(&mut app), launchers);
+    assert!(count::<Uap>(&mut app) > 0);
+
+    set_screen(&mut app, Screen::Workshop);
+    assert_eq!(scoped_to(&mut app, Screen::Launchpad), 0);
+    assert_eq!(count::<Launcher>(&mut app), 0);
+    assert_eq!(count::<Uap>(&mut app), 0);
+}
@@ -0,0 +1,146 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        bits::{Bit, ShellDesign},
+        launcher::{Projectile, ProjectileExplosionEvent},
+        score::ScoreEvent,
+        uap::{DestroyUapEvent, Hitbox, Uap},
+    },
+    game_rules::GameRules,
+};
+
+/// A launchpad round without UAPs, firing shells of the given design.
+fn staged_shell_app(design: ShellDesign) -> App {
+    let mut app = test_app();
+    app.insert_resource(GameRules {
+        max_uaps: 0,
+        ..default()
+    });
+    app.insert_resource(design);
+    set_screen(&mut app, Screen::Launchpad);
+    count_events::<ProjectileExplosionEvent>(&mut app);
+    app
+}
+
+/// A design whose shells burst after a quarter of a second into the next stage.
+fn two_stage_design() -> ShellDesign {
+    let mut design = ShellDesign::empty();
+    design.add_stage();
+    design.stage_mut(0).unwrap().step_fuse(1);
+    design
+}
+
+#[test]
+fn shells_burst_into_their_next_stage() {
+    let mut app = staged_shell_app(two_stage_design());
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 0.4);
+    assert_eq!(count::<Projectile>(&mut app), 3);
+    assert_eq!(event_count::<ProjectileExplosionEvent>(&app), 1);
+
+    advance(&mut app, 1.0);
+    assert_eq!(count::<Projectile>(&mut app), 0);
+    assert_eq!(event_count::<ProjectileExplosionEvent>(&app), 4);
+}
+
+#[test]
+fn sub_shells_split_again_into_later_stages() {
+    let mut design = two_stage_design();
+    design.add_stage();
+    design.stage_mut(2).unwrap().step_count(-1);
+    let mut app = staged_shell_app(design);
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 3.0);
+    assert_eq!(count::<Projectile>(&mut app), 0);
+    // The shell, the three it bursts into and the two each of those burst into.
+    assert_eq!(event_count::<ProjectileExplosionEvent>(&app), 1 + 3 + 3 * 2);
+}
+
+#[test]
+fn each_stage_carries_the_bits_in_its_rows() {
+    let mut design = ShellDesign::empty();
+    design.set(0, Some(Bit::Lemon));
+    design.set(63, Some(Bit::Cookie));
+    design.add_stage();
+    assert_eq!(design.stage_of(0), 0);
+    assert_eq!(design.stage_of(63), 1);
+    assert_eq!(design.stage_mix(0).dominant(), Some(Bit::Lemon.tag()));
+    assert_eq!(design.stage_mix(1).dominant(), Some(Bit::Cookie.tag()));
+
+    // Every stage keeps at least one row.
+    design.resize_stage(0, 10);
+    assert_eq!(design.stages()[0].rows, 7);
+    assert_eq!(design.stages()[1].rows, 1);
+    design.remove_stage();
+    assert_eq!(design.stages().len(), 1);
+    assert_eq!(design.stage_mix(0), design.damage_mix());
+}
+
+#[test]
+fn saved_designs_need_stages_that_divide_up_the_grid() {
+    let design = two_stage_design();
+    let saved = ron::to_string(&design).unwrap();
+    assert_eq!(ron::from_str::<ShellDesign>(&saved), Ok(design));
+
+    // Designs from before shells had stages get the single one they used to have.
+    let old = ron::from_str::<ShellDesign>("(cells: [None, Some(Lemon)])").unwrap();
+    assert_eq!(old.stages().len(), 1);
+    assert_eq!(old.stage_of(1), 0);
+
+    let with_stages = |rows: &[usize]| {
+        let stages = rows
+            .iter()
+            .map(|rows| format!("(rows: {rows}, count: 3, spread_degrees: 60.0, fuse_secs: 0.5)"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        ron::from_str::<ShellDesign>(&format!("(cells: [], stages: [{stages}])"))
+    };
+    assert!(with_stages(&[4, 4]).is_ok());
+    assert!(with_stages(&[]).is_err());
+    assert!(with_stages(&[2, 2, 2, 2]).is_err());
+    assert!(with_stages(&[4, 3]).is_err());
+    assert!(with_stages(&[8, 0]).is_err());
+}
+
+#[test]
+fn sub_shells_hitting_together_destroy_a_uap_once() {
+    // Shells that burst into three when they hit something.
+    let mut design = ShellDesign::empty();
+    design.add_stage();
+    let mut app = staged_shell_app(design);
+    count_events::<DestroyUapEvent>(&mut app);
+    count_events::<ScoreEvent>(&mut app);
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    let shell = app
+        .world_mut()
+        .query_filtered::<&Transform, With<Projectile>>()
+        .single(app.world())
+        .unwrap()
+        .translation;
+    // Just more health than the shell does damage, so that its sub-shells finish the UAP off,
+    // and so big around the shell that they all start out inside it.
+    let rules = app.world().resource::<GameRules>().clone();
+    let rules = GameRules {
+        uap_speed: 0.0,
+        uap_health: rules.projectile_damage + 1.0,
+        ..rules
+    };
+    let uap = app
+        .world_mut()
+        .spawn((
+            Uap::regular(&rules, &mut rand::thread_rng()),
+            Sprite::default(),
+            Hitbox(Vec2::splat(400.0)),
+            Transform::from_translation(shell),
+        ))
+        .id();
+
+    advance(&mut app, 1.5);
+    assert!(app.world().get_entity(uap).is_err());
+    assert_eq!(event_count::<DestroyUapEvent>(&app), 1);
+    assert_eq!(event_count::<ScoreEvent>(&app), 1);
+}
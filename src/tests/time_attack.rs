@@ -0,0 +1,104 @@
+use bevy::{input::keyboard::Key, prelude::*};
+
+use super::*;
+use crate::{
+    app::{
+        game_mode::{GameMode, RoundResults},
+        score::ScoreEvent,
+        time_attack::RoundClock,
+        uap::Uap,
+    },
+    game_rules::GameRules,
+};
+
+/// A time attack round on the launchpad with UAPs that stay put.
+fn time_attack_app() -> App {
+    let mut app = test_app();
+    app.insert_resource(GameRules {
+        max_uaps: 0,
+        uap_speed: 0.0,
+        uap_health: 20.0,
+        projectile_damage: 20.0,
+        ..default()
+    });
+    app.insert_resource(GameMode::TimeAttack);
+    set_screen(&mut app, Screen::Launchpad);
+    app
+}
+
+fn remaining_secs(app: &App) -> f32 {
+    app.world().resource::<RoundClock>().remaining_secs
+}
+
+/// How long the fixed-timestep clock the round clock runs on has been going.
+fn fixed_elapsed_secs(app: &App) -> f32 {
+    app.world().resource::<Time<Fixed>>().elapsed_secs()
+}
+
+#[test]
+fn the_round_ends_when_time_is_up() {
+    let mut app = time_attack_app();
+    assert!(remaining_secs(&app) > 89.0);
+
+    advance(&mut app, 91.0);
+    assert_eq!(
+        *app.world().resource::<State<Screen>>().get(),
+        Screen::Results
+    );
+    assert!(!app.world().contains_resource::<RoundClock>());
+    let results = app.world().resource::<RoundResults>();
+    assert_eq!(results.mode, GameMode::TimeAttack);
+    assert_eq!(results.reason, "Time up");
+}
+
+#[test]
+fn points_scored_as_time_runs_out_still_count() {
+    let mut app = time_attack_app();
+    app.world_mut().resource_mut::<RoundClock>().remaining_secs = 0.001;
+    // A kill on the tick the clock runs out.
+    app.world_mut().send_event(ScoreEvent {
+        score_to_add: 1,
+        player: None,
+    });
+    advance(&mut app, 0.1);
+    let results = app.world().resource::<RoundResults>();
+    assert_eq!(results.reason, "Time up");
+    assert_eq!(results.score, 1);
+}
+
+#[test]
+fn the_clock_stops_while_paused() {
+    let mut app = time_attack_app();
+    // Let the menus settle first, since closing them unpauses the game.
+    app.update();
+    set_pause(&mut app, true);
+    let paused_at = remaining_secs(&app);
+
+    advance(&mut app, 5.0);
+    assert_eq!(remaining_secs(&app), paused_at);
+}
+
+#[test]
+fn multi_kills_earn_bonus_time() {
+    let mut app = time_attack_app();
+    let rules = app.world().resource::<GameRules>().clone();
+    // Two UAPs hovering one above the other, right above the upright launcher.
+    for y in [0.0, 100.0] {
+        app.world_mut().spawn((
+            Uap::regular(&rules, &mut rand::thread_rng()),
+            Sprite::default(),
+            Transform::from_xyz(0.0, y, 0.0),
+        ));
+    }
+    let start = remaining_secs(&app);
+    let started = fixed_elapsed_secs(&app);
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, rules.launcher_reload_secs);
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    advance(&mut app, 2.0);
+    assert_eq!(count::<Uap>(&mut app), 0);
+    // Both kills came within the multi-kill window, so the second one added two seconds.
+    let elapsed = fixed_elapsed_secs(&app) - started;
+    assert!((remaining_secs(&app) - (start - elapsed + 2.0)).abs() < 1e-3);
+}
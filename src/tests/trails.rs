@@ -0,0 +1,37 @@
+use bevy::{input::keyboard::Key, prelude::*};
+use bevy_enoki::prelude::*;
+
+use super::*;
+use crate::{app::launcher::Projectile, game_rules::GameRules};
+
+/// The shell trails, with the entity they follow if they're still attached to a shell.
+fn trails(app: &mut App) -> Vec<(Option<Entity>, bool)> {
+    app.world_mut()
+        .query::<(&Name, Option<&ChildOf>, &ParticleSpawnerState)>()
+        .iter(app.world())
+        .filter(|(name, ..)| name.as_str() == "Shell Trail")
+        .map(|(_, child_of, state)| (child_of.map(ChildOf::parent), state.active))
+        .collect()
+}
+
+#[test]
+fn shells_leave_their_trail_behind_when_they_burst() {
+    let mut app = test_app();
+    app.insert_resource(GameRules {
+        max_uaps: 0,
+        ..default()
+    });
+    set_screen(&mut app, Screen::Launchpad);
+
+    tap_key(&mut app, KeyCode::Space, Key::Space);
+    let shell = app
+        .world_mut()
+        .query_filtered::<Entity, With<Projectile>>()
+        .single(app.world())
+        .unwrap();
+    assert_eq!(trails(&mut app), vec![(Some(shell), true)]);
+
+    advance(&mut app, 3.0);
+    assert_eq!(count::<Projectile>(&mut app), 0);
+    assert_eq!(trails(&mut app), vec![(None, false)]);
+}
@@ -1,6 +1,13 @@
-use bevy::prelude::*;
+use bevy::{
+    asset::{UntypedAssetId, VisitAssetDependencies},
+    prelude::*,
+};
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::{
+    asset_manifest::{AssetManifest, FromAssetManifest, MissingAssetKey},
+    asset_tracking::LoadResource,
+    audio::sound_effect,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
@@ -39,22 +46,26 @@ fn apply_interaction_palette(
     }
 }
 
-#[derive(Resource, Asset, Clone, Reflect)]
+#[derive(Resource, Clone, Reflect)]
 #[reflect(Resource)]
 struct InteractionAssets {
-    #[dependency]
     hover: Handle<AudioSource>,
-    #[dependency]
     click: Handle<AudioSource>,
 }
 
-impl FromWorld for InteractionAssets {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self {
-            hover: assets.load("audio/sound_effects/button_hover.ogg"),
-            click: assets.load("audio/sound_effects/button_click.ogg"),
-        }
+impl FromAssetManifest for InteractionAssets {
+    fn from_manifest(manifest: &AssetManifest) -> Result<Self, MissingAssetKey> {
+        Ok(Self {
+            hover: manifest.audio("button_hover")?,
+            click: manifest.audio("button_click")?,
+        })
+    }
+}
+
+impl VisitAssetDependencies for InteractionAssets {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        self.hover.visit_dependencies(visit);
+        self.click.visit_dependencies(visit);
     }
 }
 
@@ -14,3 +14,8 @@ pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
 /// #3d4999
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+
+/// #2b2b3a
+pub const PROGRESS_BAR_BACKGROUND: Color = Color::srgb(0.169, 0.169, 0.227);
+/// #ddd369
+pub const PROGRESS_BAR_FILL: Color = Color::srgb(0.867, 0.827, 0.412);
@@ -49,6 +49,33 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// A horizontal progress bar. Its fill starts out empty, and `fill` is added to it so that its
+/// width can be updated with the progress.
+pub fn progress_bar(fill: impl Bundle) -> impl Bundle {
+    (
+        Name::new("Progress Bar"),
+        Node {
+            width: Px(480.0),
+            height: Px(24.0),
+            padding: UiRect::all(Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(PROGRESS_BAR_BACKGROUND),
+        BorderRadius::MAX,
+        children![(
+            Name::new("Progress Bar Fill"),
+            Node {
+                width: Percent(0.0),
+                height: Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(PROGRESS_BAR_FILL),
+            BorderRadius::MAX,
+            fill,
+        )],
+    )
+}
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where